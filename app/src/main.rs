@@ -5,6 +5,7 @@ use bevy::prelude::*;
 use bevy_easings::EasingsPlugin;
 use game::data::DataPlugin;
 use game::home::HomePlugin;
+use game::save::SavePlugin;
 
 fn main() {
     #[cfg(feature = "console_error_panic_hook")]
@@ -33,6 +34,7 @@ fn main() {
         .add_plugin(EasingsPlugin)
         .add_startup_system(setup)
         .add_plugin(DataPlugin)
+        .add_plugin(SavePlugin)
         .add_plugin(HomePlugin)
         .run();
 }
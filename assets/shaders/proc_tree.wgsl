@@ -1,9 +1,12 @@
-struct CustomMaterial {
+struct ProcTreeMaterial {
     color: vec4<f32>,
+    desaturation: f32,
+    wind: f32,
+    time: f32,
 };
 
 @group(1) @binding(0)
-var<uniform> material: CustomMaterial;
+var<uniform> material: ProcTreeMaterial;
 @group(1) @binding(1)
 var base_color_texture: texture_2d<f32>;
 @group(1) @binding(2)
@@ -13,5 +16,10 @@ var base_color_sampler: sampler;
 fn fragment(
     #import bevy_sprite::mesh2d_vertex_output
 ) -> @location(0) vec4<f32> {
-    return material.color * textureSample(base_color_texture, base_color_sampler, uv);
-}
\ No newline at end of file
+    // uv.y is 0 at the top of the tree and 1 at its base, which stays put.
+    let sway = sin(material.time * 1.7 + uv.y * 3.0) * material.wind * pow(1.0 - uv.y, 2.0);
+    let texel = textureSample(base_color_texture, base_color_sampler, vec2<f32>(uv.x + sway, uv.y));
+    let luminance = dot(texel.rgb, vec3<f32>(0.299, 0.587, 0.114));
+    let rgb = mix(texel.rgb, vec3<f32>(luminance), material.desaturation);
+    return material.color * vec4<f32>(rgb, texel.a);
+}
@@ -0,0 +1,53 @@
+use bevy::prelude::*;
+use chrono::{DateTime, Duration, Utc};
+
+/// Source of wall-clock time for everything quest and tree related.
+///
+/// Systems must read the time through this resource instead of calling
+/// `Utc::now()` directly, so tests can freeze it and playtesting can skip ahead.
+#[derive(Resource)]
+pub struct GameClock {
+    mode: ClockMode,
+    offset: Duration,
+}
+
+enum ClockMode {
+    RealTime,
+    Frozen(DateTime<Utc>),
+}
+
+impl GameClock {
+    pub fn real_time() -> Self {
+        GameClock {
+            mode: ClockMode::RealTime,
+            offset: Duration::zero(),
+        }
+    }
+
+    /// A clock that stands still at `now` until it is advanced.
+    pub fn mock(now: DateTime<Utc>) -> Self {
+        GameClock {
+            mode: ClockMode::Frozen(now),
+            offset: Duration::zero(),
+        }
+    }
+
+    pub fn now(&self) -> DateTime<Utc> {
+        let base = match self.mode {
+            ClockMode::RealTime => Utc::now(),
+            ClockMode::Frozen(now) => now,
+        };
+        base + self.offset
+    }
+
+    /// Jumps the clock forward, regardless of its mode.
+    pub fn advance(&mut self, duration: Duration) {
+        self.offset = self.offset + duration;
+    }
+}
+
+impl Default for GameClock {
+    fn default() -> Self {
+        GameClock::real_time()
+    }
+}
@@ -1,14 +1,66 @@
+use crate::clock::GameClock;
 use bevy::app::{App, Plugin};
+use bevy::ecs::system::SystemParam;
 use bevy::prelude::*;
-use chrono::{DateTime, Duration, Local, Utc};
-use std::collections::VecDeque;
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::fmt;
+use std::marker::PhantomData;
 
+mod achievements;
+mod catch_up;
+mod cosmetics;
+mod duration_secs;
+mod growth;
+mod history;
+mod profile;
+mod quest_asset;
+mod quest_id;
+mod schedule;
+mod selection;
+mod streak;
+
+use achievements::evaluate_achievements;
+pub use achievements::{
+    achievement, Achievement, AchievementUnlockedEvent, Achievements, Condition, ACHIEVEMENTS,
+};
+pub use catch_up::{catch_up, AwaySummary};
+pub use cosmetics::{
+    cosmetic, leaves_for, BuyCosmeticEvent, Cosmetic, CosmeticSlot, PurchaseError,
+    ToggleCosmeticEvent, Wallet, Wardrobe, COSMETICS, LEAVES_PER_QUEST,
+};
+use cosmetics::{earn_leaves, handle_cosmetic_events};
+pub use growth::{Growth, GROWTH_STAGE_POINTS, NEGLECT_STUNT_STREAK};
+pub use history::{QuestHistory, QuestOutcome, QuestRecord};
+use profile::{gain_xp, hold_locked_quests};
+pub use profile::{
+    level_xp, unlock_level, unlocks_at, xp_for, LevelUnlock, LevelUpEvent, Profile, Unlock,
+    LEVEL_XP, UNLOCKS, XP_PER_QUEST, XP_PER_STAGE,
+};
+pub(crate) use quest_asset::load_quest_catalog;
+use quest_asset::{fill_quest_pool, load_quest_assets, QuestAssets};
+pub use quest_asset::{QuestCatalog, QuestFiles, QuestList, QuestListLoader};
+use schedule::enqueue_recurring_quests;
+pub use schedule::{
+    QuestScheduler, Schedule, ScheduleDays, ScheduleTimeZone, DEFAULT_MAX_ACTIVE_QUESTS,
+    DEFAULT_QUEST_COOLDOWN_SECS,
+};
+use selection::QuestDraw;
+pub use selection::{Fifo, QuestSelector, SelectionStrategy, WeightedRandom, DEFAULT_HISTORY_LEN};
+use streak::track_streak;
+pub use streak::{Streak, StreakRules};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum TreeKind {
     Birch,
     Oak,
+    Pine,
+    Cherry,
 }
 
-#[derive(Default)]
+/// The look of a tree, derived from its vitality.
+#[derive(Default, Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub enum Health {
     Bad,
     Moderate,
@@ -16,34 +68,117 @@ pub enum Health {
     Good,
 }
 
-impl Health {
-    pub fn decrement(&mut self) {
-        *self = match self {
-            Health::Bad => Health::Bad,
-            Health::Moderate => Health::Bad,
-            Health::Good => Health::Moderate,
-        };
-    }
+pub const MAX_VITALITY: f32 = 100.0;
 
-    pub fn increment(&mut self) {
-        *self = match self {
-            Health::Bad => Health::Moderate,
-            Health::Moderate => Health::Good,
-            Health::Good => Health::Good,
-        };
-    }
+/// Lowest vitality of a `Health::Good` tree.
+pub const GOOD_VITALITY: f32 = 66.0;
+
+/// Lowest vitality of a `Health::Moderate` tree.
+pub const MODERATE_VITALITY: f32 = 33.0;
 
-    pub fn restore(&mut self) {
-        *self = Health::Good;
+/// Vitality a living tree loses per day, whether quests come up or not.
+pub const VITALITY_DECAY_PER_DAY: f32 = 10.0;
+
+impl Health {
+    pub fn from_vitality(vitality: f32) -> Self {
+        if vitality >= GOOD_VITALITY {
+            Health::Good
+        } else if vitality >= MODERATE_VITALITY {
+            Health::Moderate
+        } else {
+            Health::Bad
+        }
     }
 }
 
-#[derive(Component)]
+#[derive(Component, Clone, Serialize, Deserialize)]
 pub struct TreeInfo {
     pub name: String,
     pub seed: u64,
-    pub health: Health,
+    /// From 0 to `MAX_VITALITY`.
+    #[serde(default = "max_vitality")]
+    pub vitality: f32,
+    /// When decay was last applied to `vitality`.
+    #[serde(default)]
+    pub vitality_updated_at: Option<DateTime<Utc>>,
     pub kind: TreeKind,
+    #[serde(default)]
+    pub growth: Growth,
+    /// Slot of the tree on the forest grid.
+    #[serde(default)]
+    pub plot: usize,
+    /// Quests missed in a row while the tree was already at `Health::Bad`.
+    #[serde(default)]
+    pub misses_at_bad: u32,
+    #[serde(default)]
+    pub died_at: Option<DateTime<Utc>>,
+}
+
+fn max_vitality() -> f32 {
+    MAX_VITALITY
+}
+
+impl TreeInfo {
+    pub fn health(&self) -> Health {
+        Health::from_vitality(self.vitality)
+    }
+
+    pub fn is_dead(&self) -> bool {
+        self.died_at.is_some()
+    }
+
+    /// Applies the passive decay since the last update.
+    pub fn decay(&mut self, now: DateTime<Utc>) {
+        if self.is_dead() {
+            return;
+        }
+        if let Some(updated_at) = self.vitality_updated_at {
+            let days = (now - updated_at).num_seconds() as f32 / 86_400.0;
+            self.vitality = (self.vitality - days.max(0.0) * VITALITY_DECAY_PER_DAY).max(0.0);
+        }
+        self.vitality_updated_at = Some(now);
+    }
+
+    pub fn complete_quest(&mut self, now: DateTime<Utc>, reward: f32, growth_points: u32) {
+        if self.is_dead() {
+            return;
+        }
+        self.decay(now);
+        self.vitality = (self.vitality + reward).min(MAX_VITALITY);
+        self.growth.grow(growth_points);
+        self.misses_at_bad = 0;
+    }
+
+    /// A skipped quest costs vitality, but unlike a miss it isn't neglect.
+    pub fn skip_quest(&mut self, now: DateTime<Utc>, penalty: f32) {
+        if self.is_dead() {
+            return;
+        }
+        self.decay(now);
+        self.vitality = (self.vitality - penalty).max(0.0);
+    }
+
+    /// Returns whether the tree died of this miss.
+    pub fn miss_quest(
+        &mut self,
+        now: DateTime<Utc>,
+        penalty: f32,
+        death_rules: &DeathRules,
+    ) -> bool {
+        if self.is_dead() {
+            return false;
+        }
+        self.decay(now);
+        if self.health() == Health::Bad {
+            self.misses_at_bad += 1;
+        }
+        self.vitality = (self.vitality - penalty).max(0.0);
+        self.growth.neglect();
+        if self.misses_at_bad >= death_rules.misses_at_bad {
+            self.died_at = Some(now);
+        }
+        self.is_dead()
+    }
 }
 
 impl Default for TreeInfo {
@@ -51,153 +186,958 @@ impl Default for TreeInfo {
         TreeInfo {
             name: "John".to_string(),
             seed: 0,
-            health: Health::default(),
+            vitality: MAX_VITALITY,
+            vitality_updated_at: None,
             kind: TreeKind::Oak,
+            growth: Growth::default(),
+            plot: 0,
+            misses_at_bad: 0,
+            died_at: None,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Difficulty {
+    Easy,
+    #[default]
+    Medium,
+    Hard,
+}
+
+impl Difficulty {
+    /// Scales the reward and penalty of a quest.
+    pub fn multiplier(&self) -> f32 {
+        match self {
+            Difficulty::Easy => 0.6,
+            Difficulty::Medium => 1.0,
+            Difficulty::Hard => 1.5,
+        }
+    }
+
+    pub fn growth_points(&self) -> u32 {
+        match self {
+            Difficulty::Easy | Difficulty::Medium => 1,
+            Difficulty::Hard => 2,
+        }
+    }
+}
+
+impl fmt::Display for Difficulty {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Difficulty::Easy => write!(f, "Easy"),
+            Difficulty::Medium => write!(f, "Medium"),
+            Difficulty::Hard => write!(f, "Hard"),
         }
     }
 }
 
-#[derive(Clone)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum QuestCategory {
+    #[default]
+    General,
+    Nature,
+    Home,
+    Body,
+    Mind,
+    Social,
+    Creative,
+    Adventure,
+}
+
+impl fmt::Display for QuestCategory {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            QuestCategory::General => write!(f, "General"),
+            QuestCategory::Nature => write!(f, "Nature"),
+            QuestCategory::Home => write!(f, "Home"),
+            QuestCategory::Body => write!(f, "Body"),
+            QuestCategory::Mind => write!(f, "Mind"),
+            QuestCategory::Social => write!(f, "Social"),
+            QuestCategory::Creative => write!(f, "Creative"),
+            QuestCategory::Adventure => write!(f, "Adventure"),
+        }
+    }
+}
+
+/// Vitality a quest gives when completed, unless it sets its own `reward`.
+pub const DEFAULT_QUEST_REWARD: f32 = 35.0;
+
+/// Vitality a quest takes when missed, unless it sets its own `penalty`.
+pub const DEFAULT_QUEST_PENALTY: f32 = 35.0;
+
+fn default_reward() -> f32 {
+    DEFAULT_QUEST_REWARD
+}
+
+fn default_penalty() -> f32 {
+    DEFAULT_QUEST_PENALTY
+}
+
+#[derive(Clone, Serialize, Deserialize)]
 pub struct Quest {
+    /// Stable identifier, taken from the name when a quest file leaves it out.
+    #[serde(default)]
+    pub id: String,
+    #[serde(default)]
     pub name: String,
+    #[serde(default)]
     pub description: String,
+    #[serde(default = "Duration::zero", with = "duration_secs")]
     pub time_to_complete: Duration,
+    /// Vitality gained when the quest is completed.
+    #[serde(default = "default_reward")]
+    pub reward: f32,
+    /// Vitality lost when the quest is missed.
+    #[serde(default = "default_penalty")]
+    pub penalty: f32,
+    #[serde(default)]
+    pub difficulty: Difficulty,
+    #[serde(default)]
+    pub category: QuestCategory,
+    /// Makes the quest recurring instead of being offered once.
+    #[serde(default)]
+    pub schedule: Option<Schedule>,
+    /// The quest is held back until all of these are met.
+    #[serde(default)]
+    pub requires: Vec<Prerequisite>,
+    /// Quests added to the pool every time this one is completed.
+    #[serde(default)]
+    pub follow_ups: Vec<Quest>,
+    /// How many times progress has to be made before the quest is complete,
+    /// like the 8 in "drink 8 glasses of water".
+    #[serde(default = "one")]
+    pub target: u32,
+    /// Named steps checked off in order. When listed, they set the target.
+    #[serde(default)]
+    pub steps: Vec<String>,
+}
+
+/// Another quest that has to be completed some number of times first.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Prerequisite {
+    /// Id of the quest to complete.
+    pub quest: String,
+    #[serde(default = "one")]
+    pub times: u32,
+}
+
+fn one() -> u32 {
+    1
+}
+
+impl Default for Quest {
+    fn default() -> Self {
+        Quest {
+            id: String::new(),
+            name: String::new(),
+            description: String::new(),
+            time_to_complete: Duration::zero(),
+            reward: DEFAULT_QUEST_REWARD,
+            penalty: DEFAULT_QUEST_PENALTY,
+            difficulty: Difficulty::default(),
+            category: QuestCategory::default(),
+            schedule: None,
+            requires: Vec::new(),
+            follow_ups: Vec::new(),
+            target: 1,
+            steps: Vec::new(),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum QuestValidationError {
+    MissingName,
+    ZeroTimeToComplete,
+    NegativeWeight,
+    ZeroTarget,
+}
+
+impl fmt::Display for QuestValidationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            QuestValidationError::MissingName => write!(f, "quest has no name"),
+            QuestValidationError::ZeroTimeToComplete => {
+                write!(f, "quest has a zero time_to_complete")
+            }
+            QuestValidationError::NegativeWeight => {
+                write!(f, "quest has a negative reward or penalty")
+            }
+            QuestValidationError::ZeroTarget => write!(f, "quest has a zero target"),
+        }
+    }
 }
 
-#[derive(Clone)]
+impl Quest {
+    /// Vitality gained when completed, scaled by difficulty.
+    pub fn scaled_reward(&self) -> f32 {
+        self.reward * self.difficulty.multiplier()
+    }
+
+    /// Vitality lost when missed, scaled by difficulty.
+    pub fn scaled_penalty(&self) -> f32 {
+        self.penalty * self.difficulty.multiplier()
+    }
+
+    pub fn validate(&self) -> Result<(), QuestValidationError> {
+        if self.name.trim().is_empty() {
+            return Err(QuestValidationError::MissingName);
+        }
+        if self.time_to_complete <= Duration::zero() {
+            return Err(QuestValidationError::ZeroTimeToComplete);
+        }
+        if self.reward < 0.0 || self.penalty < 0.0 {
+            return Err(QuestValidationError::NegativeWeight);
+        }
+        if self.target() == 0 {
+            return Err(QuestValidationError::ZeroTarget);
+        }
+        self.follow_ups.iter().try_for_each(Quest::validate)
+    }
+
+    /// Progress needed to complete the quest.
+    pub fn target(&self) -> u32 {
+        match self.steps.len() {
+            0 => self.target,
+            steps => steps as u32,
+        }
+    }
+
+    /// Takes the id from the name wherever a quest file leaves it out.
+    pub fn fill_ids(&mut self) {
+        if self.id.is_empty() {
+            self.id = self.name.clone();
+        }
+        self.follow_ups.iter_mut().for_each(Quest::fill_ids);
+    }
+}
+
+#[derive(Clone, Serialize, Deserialize)]
 pub struct ActiveQuest {
+    #[serde(with = "quest_id")]
     pub quest: Quest,
+    /// Saved rather than worked out from the deadline, which a quest file changing the
+    /// time to complete would shift.
+    pub appeared_at: DateTime<Utc>,
     pub deadline: DateTime<Utc>,
+    /// Steps done or count reached so far, out of `Quest::target`.
+    #[serde(default)]
+    pub progress: u32,
 }
 
-impl From<Quest> for ActiveQuest {
-    fn from(quest: Quest) -> Self {
+impl ActiveQuest {
+    pub fn new(quest: Quest, appeared_at: DateTime<Utc>) -> Self {
         ActiveQuest {
-            quest: quest.clone(),
-            deadline: DateTime::<Utc>::from(Local::now()) + quest.time_to_complete,
+            appeared_at,
+            deadline: appeared_at + quest.time_to_complete,
+            quest,
+            progress: 0,
         }
     }
+
+    /// Makes one step of progress. Returns whether the target has been reached.
+    pub fn advance(&mut self) -> bool {
+        self.progress = (self.progress + 1).min(self.quest.target());
+        self.is_done()
+    }
+
+    pub fn is_done(&self) -> bool {
+        self.progress >= self.quest.target()
+    }
 }
 
-#[derive(Resource, Clone, Default)]
+#[derive(Resource, Clone, Default, Serialize, Deserialize)]
 pub struct QuestPool {
+    #[serde(with = "quest_id::seq")]
     pub queue: VecDeque<Quest>,
+    /// Ids of the one-shot quests from the quest files that have been queued, so only
+    /// quests added to the files later are queued into a restored pool.
+    #[serde(default)]
+    pub introduced: HashSet<String>,
+    /// How many times each quest has been completed, by quest id.
+    #[serde(default)]
+    pub completions: HashMap<String, u32>,
+    /// Quests put off for later, waiting to go back into the queue.
+    #[serde(default)]
+    pub snoozed: Vec<SnoozedQuest>,
+    /// Ids of quests taken out of rotation for good.
+    #[serde(default)]
+    pub rejected: HashSet<String>,
+    /// Quests of categories the player hasn't reached the level for yet.
+    #[serde(default, with = "quest_id::seq")]
+    pub locked: Vec<Quest>,
 }
 
-#[derive(Resource, Default)]
+#[derive(Clone, Serialize, Deserialize)]
+pub struct SnoozedQuest {
+    #[serde(with = "quest_id")]
+    pub quest: Quest,
+    pub until: DateTime<Utc>,
+}
+
+impl QuestPool {
+    /// Whether `quest` can be offered: not rejected and with its prerequisites met.
+    pub fn is_available(&self, quest: &Quest) -> bool {
+        !self.rejected.contains(&quest.id) && self.is_unlocked(quest)
+    }
+
+    /// Whether every prerequisite of `quest` has been completed often enough.
+    pub fn is_unlocked(&self, quest: &Quest) -> bool {
+        quest.requires.iter().all(|prerequisite| {
+            self.completions
+                .get(&prerequisite.quest)
+                .is_some_and(|completions| *completions >= prerequisite.times)
+        })
+    }
+
+    /// Counts a completion of `quest` and queues its follow-ups.
+    pub fn record_completion(&mut self, quest: &Quest) {
+        *self.completions.entry(quest.id.clone()).or_default() += 1;
+        self.queue.extend(quest.follow_ups.iter().cloned());
+    }
+
+    /// Moves snoozed quests whose time has come back to the end of the queue.
+    pub fn wake_snoozed(&mut self, now: DateTime<Utc>) {
+        let (awake, snoozed) = self
+            .snoozed
+            .drain(..)
+            .partition(|snoozed_quest| snoozed_quest.until <= now);
+        self.snoozed = snoozed;
+        self.queue.extend(
+            awake
+                .into_iter()
+                .map(|snoozed_quest: SnoozedQuest| snoozed_quest.quest),
+        );
+    }
+
+    /// Whether every queued quest is unlocked for `profile` and every held back one is not.
+    pub fn is_held_for(&self, profile: &Profile) -> bool {
+        let unlocked = |quest: &Quest| profile.has_unlocked(Unlock::QuestCategory(quest.category));
+        self.queue.iter().all(unlocked) && !self.locked.iter().any(unlocked)
+    }
+
+    /// Moves quests of categories locked for `profile` out of the queue, and queues
+    /// the held back ones it has unlocked since.
+    pub fn hold_for(&mut self, profile: &Profile) {
+        let unlocked = |quest: &Quest| profile.has_unlocked(Unlock::QuestCategory(quest.category));
+        let (queued, newly_locked): (VecDeque<Quest>, VecDeque<Quest>) =
+            self.queue.drain(..).partition(|quest| unlocked(quest));
+        let (newly_unlocked, locked): (Vec<Quest>, Vec<Quest>) =
+            self.locked.drain(..).partition(|quest| unlocked(quest));
+        self.queue = queued;
+        self.queue.extend(newly_unlocked);
+        self.locked = locked;
+        for quest in newly_locked {
+            // Recurring quests come back every occurrence, one copy is enough.
+            if self.locked.iter().all(|held| held.id != quest.id) {
+                self.locked.push(quest);
+            }
+        }
+    }
+
+    /// Takes `quest` out of rotation, along with any copies still waiting.
+    pub fn reject(&mut self, quest: &Quest) {
+        self.rejected.insert(quest.id.clone());
+        self.queue.retain(|queued| queued.id != quest.id);
+        self.snoozed
+            .retain(|snoozed_quest| snoozed_quest.quest.id != quest.id);
+        self.locked.retain(|held| held.id != quest.id);
+    }
+}
+
+#[derive(Resource, Clone, Default, Serialize, Deserialize)]
 pub struct CurrentQuestInfo {
-    pub current_quest: Option<ActiveQuest>,
+    /// Quests on offer, in the order they appeared.
+    pub active_quests: Vec<ActiveQuest>,
     pub last_quest_finished: DateTime<Utc>,
 }
 
+impl CurrentQuestInfo {
+    pub fn get(&self, quest_id: &str) -> Option<&ActiveQuest> {
+        self.active_quests
+            .iter()
+            .find(|active_quest| active_quest.quest.id == quest_id)
+    }
+
+    pub fn get_mut(&mut self, quest_id: &str) -> Option<&mut ActiveQuest> {
+        self.active_quests
+            .iter_mut()
+            .find(|active_quest| active_quest.quest.id == quest_id)
+    }
+
+    /// Removes the active quest with `quest_id`, finishing it at `now`.
+    pub fn finish(&mut self, quest_id: &str, now: DateTime<Utc>) -> Option<ActiveQuest> {
+        let index = self
+            .active_quests
+            .iter()
+            .position(|active_quest| active_quest.quest.id == quest_id)?;
+        self.last_quest_finished = now;
+        Some(self.active_quests.remove(index))
+    }
+
+    /// Earliest time another quest may appear: a cooldown after the last one
+    /// appeared or finished, whichever was later.
+    pub fn next_quest_at(&self, cooldown: Duration) -> DateTime<Utc> {
+        let last_quest_appeared = self
+            .active_quests
+            .iter()
+            .map(|active_quest| active_quest.appeared_at)
+            .max();
+        last_quest_appeared.map_or(self.last_quest_finished, |appeared_at| {
+            appeared_at.max(self.last_quest_finished)
+        }) + cooldown
+    }
+}
+
 #[derive(Bundle, Default)]
 pub struct TreeItem {
     pub info: TreeInfo,
 }
 
+/// The selected tree, which quest results apply to.
 #[derive(Resource)]
 pub struct CurrentTree(pub Entity);
 
-pub struct QuestAppearedEvent;
+/// The selected tree, looked up to apply quest results to it.
+#[derive(SystemParam)]
+struct SelectedTree<'w, 's> {
+    current_tree: Res<'w, CurrentTree>,
+    trees: Query<'w, 's, &'static mut TreeInfo>,
+}
+
+impl SelectedTree<'_, '_> {
+    fn entity(&self) -> Entity {
+        self.current_tree.0
+    }
+
+    fn info_mut(&mut self) -> Option<Mut<'_, TreeInfo>> {
+        self.trees.get_mut(self.current_tree.0).ok()
+    }
+
+    /// While it is, the replant screen is up and quests wait for the new tree.
+    fn is_dead(&self) -> bool {
+        self.trees
+            .get(self.current_tree.0)
+            .is_ok_and(|tree| tree.is_dead())
+    }
+}
+
+/// What a completion counts towards besides the tree.
+#[derive(SystemParam)]
+struct CompletionRecords<'w, 's> {
+    history: ResMut<'w, QuestHistory>,
+    streak: ResMut<'w, Streak>,
+    streak_rules: Res<'w, StreakRules>,
+    #[system_param(ignore)]
+    _marker: PhantomData<&'s ()>,
+}
+
+/// How many trees fit on the forest grid.
+pub const MAX_TREES: usize = 9;
+
+const TREE_NAMES: [&str; MAX_TREES] = [
+    "John", "Hazel", "Rowan", "Willow", "Aspen", "Linden", "Alder", "Ash", "Elm",
+];
+
+/// A new tree may be planted once every tree in the forest is fully grown.
+pub fn can_plant<'a>(mut trees: impl ExactSizeIterator<Item = &'a TreeInfo>) -> bool {
+    trees.len() < MAX_TREES && trees.all(|tree| tree.growth.is_fully_grown() && !tree.is_dead())
+}
+
+/// Index of the first name from `first_index` on that no tree holds. With every name
+/// taken, the one at `first_index` is reused.
+fn free_name_index(first_index: usize, trees: &Query<&TreeInfo>) -> usize {
+    (first_index..first_index + TREE_NAMES.len())
+        .find(|index| {
+            let name = TREE_NAMES[index % TREE_NAMES.len()];
+            trees.iter().all(|tree| tree.name != name)
+        })
+        .unwrap_or(first_index)
+}
+
+fn new_tree(plot: usize, name_index: usize, kind: TreeKind, now: DateTime<Utc>) -> TreeInfo {
+    TreeInfo {
+        name: TREE_NAMES[name_index % TREE_NAMES.len()].to_string(),
+        seed: now.timestamp_millis() as u64 ^ plot as u64,
+        kind,
+        vitality_updated_at: Some(now),
+        growth: Growth::planted(now),
+        plot,
+        ..default()
+    }
+}
+
+#[derive(Resource, Clone)]
+pub struct DeathRules {
+    /// Quests missed in a row at `Health::Bad` before the tree dies.
+    pub misses_at_bad: u32,
+}
+
+impl Default for DeathRules {
+    fn default() -> Self {
+        DeathRules { misses_at_bad: 3 }
+    }
+}
+
+/// What declining the current quest costs.
+#[derive(Resource, Clone)]
+pub struct QuestActionRules {
+    /// How long a snoozed quest waits before it goes back into the queue.
+    pub snooze_for: Duration,
+    /// Share of the miss penalty taken for skipping a quest.
+    pub skip_penalty_share: f32,
+}
+
+impl Default for QuestActionRules {
+    fn default() -> Self {
+        QuestActionRules {
+            snooze_for: Duration::hours(1),
+            skip_penalty_share: 0.4,
+        }
+    }
+}
+
+/// A tree that died and was cleared away to be replanted.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct MemorialEntry {
+    pub name: String,
+    pub kind: TreeKind,
+    pub stage: usize,
+    pub planted_at: Option<DateTime<Utc>>,
+    pub died_at: DateTime<Utc>,
+}
+
+#[derive(Resource, Clone, Default, Serialize, Deserialize)]
+pub struct Memorial {
+    pub entries: Vec<MemorialEntry>,
+}
+
+/// Sent when a quest comes up for the selected tree.
+pub struct QuestAppearedEvent {
+    pub quest_id: String,
+    /// The tree the quest is for.
+    pub tree: Entity,
+    pub at: DateTime<Utc>,
+    pub deadline: DateTime<Utc>,
+}
+
+/// One step of progress on a quest. Completes it once the target is reached.
+pub struct QuestProgressEvent {
+    pub quest_id: String,
+}
+
+/// Sent once a completed quest has been applied to its tree.
+pub struct QuestCompletedEvent {
+    pub quest_id: String,
+    pub tree: Entity,
+    pub at: DateTime<Utc>,
+    pub difficulty: Difficulty,
+    /// Health of the tree before the quest was applied to it.
+    pub health_before: Health,
+    /// Vitality gained, less whatever the tree couldn't take above its maximum.
+    pub vitality_gained: f32,
+    pub growth_points: u32,
+    /// The growth stage the tree reached, if the quest made it grow into a new one.
+    pub new_stage: Option<usize>,
+}
+
+/// Sent once a missed quest has been applied to its tree.
+pub struct QuestMissedEvent {
+    pub quest_id: String,
+    pub tree: Entity,
+    pub at: DateTime<Utc>,
+    pub vitality_lost: f32,
+    pub tree_died: bool,
+}
+
+/// Puts a quest off for `QuestActionRules::snooze_for`.
+pub struct QuestSnoozedEvent {
+    pub quest_id: String,
+}
+
+/// Drops a quest for a smaller penalty than missing it.
+pub struct QuestSkippedEvent {
+    pub quest_id: String,
+}
+
+/// Drops a quest and never offers it again.
+pub struct QuestRejectedEvent {
+    pub quest_id: String,
+}
 
-pub struct QuestCompletedEvent;
+pub struct PlantTreeEvent;
 
-pub struct QuestMissedEvent;
+/// Clears away the dead selected tree and plants a fresh one in its plot.
+pub struct ReplantTreeEvent;
 
 pub struct DataPlugin;
 
 impl Plugin for DataPlugin {
     fn build(&self, app: &mut App) {
         app.add_startup_system(current_tree_setup)
+            .init_resource::<GameClock>()
+            .init_resource::<DeathRules>()
+            .init_resource::<QuestActionRules>()
+            .init_resource::<Memorial>()
+            .init_resource::<QuestHistory>()
+            .init_resource::<Streak>()
+            .init_resource::<StreakRules>()
+            .init_resource::<Wallet>()
+            .init_resource::<Wardrobe>()
+            .init_resource::<Profile>()
+            .init_resource::<Achievements>()
             .insert_resource(CurrentQuestInfo::default())
             .insert_resource(QuestPool::default())
-            .add_startup_system(fill_quest_pool)
+            .insert_resource(QuestAssets::default())
+            .init_resource::<QuestCatalog>()
+            .init_resource::<QuestFiles>()
+            .init_resource::<QuestScheduler>()
+            .init_resource::<QuestSelector>()
+            .add_asset::<QuestList>()
+            .init_asset_loader::<QuestListLoader>()
+            .add_startup_system(load_quest_assets)
+            .add_system_to_stage(CoreStage::PreUpdate, load_quest_catalog)
+            .add_system(fill_quest_pool.before(check_next_quest))
+            .add_system(enqueue_recurring_quests.before(check_next_quest))
+            .add_event::<QuestProgressEvent>()
             .add_event::<QuestCompletedEvent>()
             .add_event::<QuestMissedEvent>()
+            .add_event::<QuestSnoozedEvent>()
+            .add_event::<QuestSkippedEvent>()
+            .add_event::<QuestRejectedEvent>()
             .add_event::<QuestAppearedEvent>()
-            .add_system(handle_events)
+            .add_event::<PlantTreeEvent>()
+            .add_event::<ReplantTreeEvent>()
+            .add_event::<BuyCosmeticEvent>()
+            .add_event::<ToggleCosmeticEvent>()
+            .add_event::<LevelUpEvent>()
+            .add_event::<AchievementUnlockedEvent>()
+            .add_system(plant_tree)
+            .add_system(replant_tree)
+            .add_system(advance_quest_progress)
+            .add_system(decay_vitality)
+            .add_system(handle_declined_quests.before(check_next_quest))
+            .add_system(handle_skipped_quests.before(check_next_quest))
+            .add_system(wake_snoozed_quests.before(check_next_quest))
             .add_system(check_deadline)
-            .add_system(check_next_quest);
+            .add_system(earn_leaves.after(advance_quest_progress))
+            .add_system(handle_cosmetic_events.after(earn_leaves))
+            .add_system(gain_xp.after(advance_quest_progress))
+            .add_system(
+                hold_locked_quests
+                    .after(fill_quest_pool)
+                    .after(enqueue_recurring_quests)
+                    .after(wake_snoozed_quests)
+                    .after(gain_xp)
+                    .before(check_next_quest),
+            )
+            .add_system(track_streak.after(check_deadline))
+            .add_system(check_next_quest)
+            // After the quest results of the frame have been applied to the trees.
+            .add_system_to_stage(CoreStage::PostUpdate, evaluate_achievements);
     }
 }
 
-fn current_tree_setup(mut commands: Commands) {
-    let default_tree = commands.spawn(TreeItem::default()).id();
+fn current_tree_setup(
+    mut commands: Commands,
+    current_tree: Option<Res<CurrentTree>>,
+    clock: Res<GameClock>,
+) {
+    if current_tree.is_some() {
+        return;
+    }
+
+    let default_tree = commands
+        .spawn(TreeItem {
+            info: TreeInfo {
+                vitality_updated_at: Some(clock.now()),
+                growth: Growth::planted(clock.now()),
+                ..default()
+            },
+        })
+        .id();
 
     commands.insert_resource(CurrentTree(default_tree));
 }
 
-fn handle_events(
-    quest_completed_events: EventReader<QuestCompletedEvent>,
-    quest_missed_events: EventReader<QuestMissedEvent>,
-    mut current_quest_info: ResMut<CurrentQuestInfo>,
+fn plant_tree(
+    mut commands: Commands,
+    mut plant_tree_events: EventReader<PlantTreeEvent>,
+    trees: Query<&TreeInfo>,
+    profile: Res<Profile>,
+    clock: Res<GameClock>,
+) {
+    if plant_tree_events.iter().count() == 0 || !can_plant(trees.iter()) {
+        return;
+    }
+
+    let plot = (0..MAX_TREES)
+        .find(|plot| trees.iter().all(|tree| tree.plot != *plot))
+        .unwrap();
+    let tree = commands
+        .spawn(TreeItem {
+            info: new_tree(
+                plot,
+                free_name_index(plot, &trees),
+                profile.tree_kind_for(plot),
+                clock.now(),
+            ),
+        })
+        .id();
+    commands.insert_resource(CurrentTree(tree));
+}
+
+fn replant_tree(
+    mut commands: Commands,
+    mut replant_tree_events: EventReader<ReplantTreeEvent>,
     current_tree: Res<CurrentTree>,
-    mut tree_items: Query<&mut TreeInfo>,
+    trees: Query<&TreeInfo>,
+    mut memorial: ResMut<Memorial>,
+    profile: Res<Profile>,
+    clock: Res<GameClock>,
 ) {
-    let mut current_tree_item = tree_items.get_mut(current_tree.0).unwrap();
-    let quest_completed = !quest_completed_events.is_empty();
-    let quest_missed = !quest_missed_events.is_empty();
+    if replant_tree_events.iter().count() == 0 {
+        return;
+    }
+    let Ok(dead_tree) = trees.get(current_tree.0) else {
+        return;
+    };
+    let Some(died_at) = dead_tree.died_at else {
+        return;
+    };
 
-    if quest_completed || quest_missed {
-        current_quest_info.current_quest = None;
-        current_quest_info.last_quest_finished = DateTime::from(Utc::now());
+    memorial.entries.push(MemorialEntry {
+        name: dead_tree.name.clone(),
+        kind: dead_tree.kind,
+        stage: dead_tree.growth.stage,
+        planted_at: dead_tree.growth.planted_at,
+        died_at,
+    });
+    // Skip ahead in the name list, so the new tree doesn't take the name of the one
+    // it replaces.
+    let name_index = free_name_index(dead_tree.plot + memorial.entries.len(), &trees);
+    let tree = commands
+        .spawn(TreeItem {
+            info: new_tree(
+                dead_tree.plot,
+                name_index,
+                profile.tree_kind_for(dead_tree.plot),
+                clock.now(),
+            ),
+        })
+        .id();
+    commands.entity(current_tree.0).despawn();
+    commands.insert_resource(CurrentTree(tree));
+}
 
-        if quest_completed {
-            current_tree_item.health.increment()
-        } else {
-            current_tree_item.health.decrement()
+/// Completes quests once their target is reached and applies them to the selected tree.
+fn advance_quest_progress(
+    mut quest_progress_events: EventReader<QuestProgressEvent>,
+    mut quest_completed_events: EventWriter<QuestCompletedEvent>,
+    mut current_quest_info: ResMut<CurrentQuestInfo>,
+    mut quest_pool: ResMut<QuestPool>,
+    mut records: CompletionRecords,
+    mut selected_tree: SelectedTree,
+    clock: Res<GameClock>,
+) {
+    let now = clock.now();
+    for event in quest_progress_events.iter() {
+        let Some(active_quest) = current_quest_info.get_mut(&event.quest_id) else {
+            continue;
+        };
+        if !active_quest.advance() {
+            continue;
         }
+        let Some(active_quest) = current_quest_info.finish(&event.quest_id, now) else {
+            continue;
+        };
+        records
+            .history
+            .record(&active_quest, now, QuestOutcome::Completed);
+        let quest = active_quest.quest;
+        quest_pool.record_completion(&quest);
+        records.streak.complete(&records.streak_rules);
+
+        let tree_entity = selected_tree.entity();
+        let Some(mut tree) = selected_tree.info_mut() else {
+            continue;
+        };
+        // Settle the decay first, so it doesn't count against the reward.
+        tree.decay(now);
+        let (vitality_before, stage_before) = (tree.vitality, tree.growth.stage);
+        let health_before = tree.health();
+        // The streak bonus is part of the completion, so the event reports what it brought.
+        let reward =
+            quest.scaled_reward() * (1.0 + records.streak.vitality_bonus(&records.streak_rules));
+        let growth_points =
+            quest.difficulty.growth_points() + records.streak.growth_bonus(&records.streak_rules);
+        tree.complete_quest(now, reward, growth_points);
+        quest_completed_events.send(QuestCompletedEvent {
+            quest_id: quest.id,
+            tree: tree_entity,
+            at: now,
+            difficulty: quest.difficulty,
+            health_before,
+            vitality_gained: (tree.vitality - vitality_before).max(0.0),
+            growth_points,
+            new_stage: (tree.growth.stage > stage_before).then_some(tree.growth.stage),
+        });
     }
 }
 
-fn check_deadline(
-    current_quest: ResMut<CurrentQuestInfo>,
-    mut quest_missed_events: EventWriter<QuestMissedEvent>,
+fn handle_declined_quests(
+    mut quest_snoozed_events: EventReader<QuestSnoozedEvent>,
+    mut quest_rejected_events: EventReader<QuestRejectedEvent>,
+    mut current_quest_info: ResMut<CurrentQuestInfo>,
+    mut quest_pool: ResMut<QuestPool>,
+    mut history: ResMut<QuestHistory>,
+    rules: Res<QuestActionRules>,
+    clock: Res<GameClock>,
 ) {
-    if let Some(active_quest) = current_quest.current_quest.as_ref() {
-        if DateTime::<Utc>::from(Local::now()) > active_quest.deadline {
-            quest_missed_events.send(QuestMissedEvent)
+    let now = clock.now();
+    for event in quest_snoozed_events.iter() {
+        if let Some(active_quest) = current_quest_info.finish(&event.quest_id, now) {
+            quest_pool.snoozed.push(SnoozedQuest {
+                quest: active_quest.quest,
+                until: now + rules.snooze_for,
+            });
+        }
+    }
+    for event in quest_rejected_events.iter() {
+        if let Some(active_quest) = current_quest_info.finish(&event.quest_id, now) {
+            history.record(&active_quest, now, QuestOutcome::Rejected);
+            quest_pool.reject(&active_quest.quest);
         }
     }
 }
 
-fn check_next_quest(
+fn handle_skipped_quests(
+    mut quest_skipped_events: EventReader<QuestSkippedEvent>,
     mut current_quest_info: ResMut<CurrentQuestInfo>,
-    mut quest_appeared_events: EventWriter<QuestAppearedEvent>,
     mut quest_pool: ResMut<QuestPool>,
+    mut history: ResMut<QuestHistory>,
+    mut selected_tree: SelectedTree,
+    rules: Res<QuestActionRules>,
+    clock: Res<GameClock>,
 ) {
-    let since_last_quest_finished =
-        DateTime::from(Utc::now()) - current_quest_info.last_quest_finished;
-    if current_quest_info.current_quest.is_none()
-        && since_last_quest_finished > Duration::seconds(5)
+    let now = clock.now();
+    for event in quest_skipped_events.iter() {
+        let Some(active_quest) = current_quest_info.finish(&event.quest_id, now) else {
+            continue;
+        };
+        if selected_tree.is_dead() {
+            quest_pool.queue.push_front(active_quest.quest);
+            continue;
+        }
+        history.record(&active_quest, now, QuestOutcome::Skipped);
+        if let Some(mut tree) = selected_tree.info_mut() {
+            let penalty = active_quest.quest.scaled_penalty() * rules.skip_penalty_share;
+            tree.skip_quest(now, penalty);
+        }
+    }
+}
+
+fn wake_snoozed_quests(mut quest_pool: ResMut<QuestPool>, clock: Res<GameClock>) {
+    let now = clock.now();
+    if quest_pool
+        .snoozed
+        .iter()
+        .any(|snoozed_quest| snoozed_quest.until <= now)
     {
-        if let Some(quest) = quest_pool.queue.pop_front() {
-            current_quest_info.current_quest = Some(quest.into());
-            quest_appeared_events.send(QuestAppearedEvent);
+        quest_pool.wake_snoozed(now);
+    }
+}
+
+/// How often the passive vitality decay is applied to living trees.
+const DECAY_INTERVAL_SECS: i64 = 60;
+
+fn decay_vitality(mut trees: Query<&mut TreeInfo>, clock: Res<GameClock>) {
+    let now = clock.now();
+    for mut tree in trees.iter_mut() {
+        let due = tree
+            .vitality_updated_at
+            .is_none_or(|updated_at| now - updated_at >= Duration::seconds(DECAY_INTERVAL_SECS));
+        if due && !tree.is_dead() {
+            tree.decay(now);
         }
     }
 }
 
-fn fill_quest_pool(
+/// Misses quests whose deadline has passed and applies them to the selected tree.
+fn check_deadline(
+    mut current_quest_info: ResMut<CurrentQuestInfo>,
+    mut quest_missed_events: EventWriter<QuestMissedEvent>,
     mut quest_pool: ResMut<QuestPool>,
+    mut history: ResMut<QuestHistory>,
+    mut selected_tree: SelectedTree,
+    death_rules: Res<DeathRules>,
+    clock: Res<GameClock>,
+) {
+    let now = clock.now();
+    let expired: Vec<String> = current_quest_info
+        .active_quests
+        .iter()
+        .filter(|active_quest| now > active_quest.deadline)
+        .map(|active_quest| active_quest.quest.id.clone())
+        .collect();
+
+    for quest_id in expired {
+        let Some(active_quest) = current_quest_info.finish(&quest_id, now) else {
+            continue;
+        };
+        // A dead tree can't be let down any further, so the quest isn't missed and
+        // doesn't cost the streak. It comes back first for the replanted tree.
+        if selected_tree.is_dead() {
+            quest_pool.queue.push_front(active_quest.quest);
+            continue;
+        }
+        history.record(&active_quest, now, QuestOutcome::Missed);
+        let tree_entity = selected_tree.entity();
+        let Some(mut tree) = selected_tree.info_mut() else {
+            continue;
+        };
+        tree.decay(now);
+        let vitality_before = tree.vitality;
+        let tree_died = tree.miss_quest(now, active_quest.quest.scaled_penalty(), &death_rules);
+        quest_missed_events.send(QuestMissedEvent {
+            quest_id,
+            tree: tree_entity,
+            at: now,
+            vitality_lost: (vitality_before - tree.vitality).max(0.0),
+            tree_died,
+        });
+    }
+}
+
+fn check_next_quest(
     mut current_quest_info: ResMut<CurrentQuestInfo>,
     mut quest_appeared_events: EventWriter<QuestAppearedEvent>,
+    mut quest_draw: QuestDraw,
+    current_tree: Res<CurrentTree>,
+    trees: Query<&TreeInfo>,
+    scheduler: Res<QuestScheduler>,
+    clock: Res<GameClock>,
 ) {
-    quest_pool.queue.push_back(Quest {
-        name: "Test".to_string(),
-        description: "... test".to_string(),
-        time_to_complete: Duration::seconds(30),
-    });
-    quest_pool.queue.push_back(Quest {
-        name: "Cheer up".to_string(),
-        description: "No ecological revolution can be done in a bad mood".to_string(),
-        time_to_complete: Duration::seconds(8),
-    });
-    quest_pool.queue.push_back(Quest {
-        name: "Tidy up".to_string(),
-        description: "Clean your place after submitting your JacobsHack entry".to_string(),
-        time_to_complete: Duration::seconds(30),
-    });
-    current_quest_info.current_quest = Some(quest_pool.queue.pop_front().unwrap().into());
-    quest_appeared_events.send(QuestAppearedEvent);
+    // A dead tree gets no more quests until it is replanted.
+    if trees.get(current_tree.0).map_or(true, TreeInfo::is_dead) {
+        return;
+    }
+
+    let now = clock.now();
+    if current_quest_info.active_quests.len() < scheduler.max_active
+        && now > current_quest_info.next_quest_at(scheduler.cooldown)
+    {
+        if let Some(quest) = quest_draw.next(&current_quest_info) {
+            let active_quest = ActiveQuest::new(quest, now);
+            quest_appeared_events.send(QuestAppearedEvent {
+                quest_id: active_quest.quest.id.clone(),
+                tree: current_tree.0,
+                at: now,
+                deadline: active_quest.deadline,
+            });
+            current_quest_info.active_quests.push(active_quest);
+        }
+    }
 }
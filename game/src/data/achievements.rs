@@ -0,0 +1,116 @@
+//! Milestones unlocked once over the whole game, like the first completed quest.
+
+use super::{Health, QuestCompletedEvent, QuestHistory, QuestScheduler, TreeInfo, TreeKind};
+use bevy::prelude::*;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// What it takes to unlock an achievement.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Condition {
+    QuestsCompleted(u32),
+    /// Days in a row with at least one completed quest.
+    DayStreak(u32),
+    /// A living tree of the kind has reached its last growth stage.
+    FullyGrown(TreeKind),
+    /// A quest completed on a tree at `Health::Bad` pulled it out of there.
+    RecoveredFromBad,
+}
+
+pub struct Achievement {
+    /// Stable identifier the unlock is saved under.
+    pub id: &'static str,
+    pub name: &'static str,
+    pub description: &'static str,
+    pub condition: Condition,
+}
+
+pub static ACHIEVEMENTS: [Achievement; 4] = [
+    Achievement {
+        id: "first_quest",
+        name: "First steps",
+        description: "Complete your first quest",
+        condition: Condition::QuestsCompleted(1),
+    },
+    Achievement {
+        id: "ten_day_streak",
+        name: "Creature of habit",
+        description: "Complete quests 10 days in a row",
+        condition: Condition::DayStreak(10),
+    },
+    Achievement {
+        id: "grown_birch",
+        name: "Silver bark",
+        description: "Grow a Birch to full size",
+        condition: Condition::FullyGrown(TreeKind::Birch),
+    },
+    Achievement {
+        id: "survived_bad",
+        name: "Back from the brink",
+        description: "Nurse a tree out of bad health",
+        condition: Condition::RecoveredFromBad,
+    },
+];
+
+pub fn achievement(id: &str) -> Option<&'static Achievement> {
+    ACHIEVEMENTS.iter().find(|achievement| achievement.id == id)
+}
+
+/// When each unlocked achievement was unlocked, by achievement id.
+#[derive(Resource, Clone, Default, Serialize, Deserialize)]
+pub struct Achievements {
+    pub unlocked: HashMap<String, DateTime<Utc>>,
+}
+
+impl Achievements {
+    pub fn is_unlocked(&self, id: &str) -> bool {
+        self.unlocked.contains_key(id)
+    }
+}
+
+pub struct AchievementUnlockedEvent {
+    pub id: &'static str,
+    pub at: DateTime<Utc>,
+}
+
+pub(crate) fn evaluate_achievements(
+    mut quest_completed_events: EventReader<QuestCompletedEvent>,
+    mut achievement_unlocked_events: EventWriter<AchievementUnlockedEvent>,
+    mut achievements: ResMut<Achievements>,
+    history: Res<QuestHistory>,
+    scheduler: Res<QuestScheduler>,
+    trees: Query<&TreeInfo>,
+) {
+    for event in quest_completed_events.iter() {
+        for achievement in &ACHIEVEMENTS {
+            if achievements.is_unlocked(achievement.id) {
+                continue;
+            }
+            let met = match achievement.condition {
+                Condition::QuestsCompleted(count) => history.completed().count() >= count as usize,
+                Condition::DayStreak(days) => {
+                    history.longest_day_streak(scheduler.time_zone) >= days
+                }
+                Condition::FullyGrown(kind) => trees.iter().any(|tree| {
+                    tree.kind == kind && tree.growth.is_fully_grown() && !tree.is_dead()
+                }),
+                Condition::RecoveredFromBad => {
+                    event.health_before == Health::Bad
+                        && trees
+                            .get(event.tree)
+                            .is_ok_and(|tree| tree.health() != Health::Bad)
+                }
+            };
+            if met {
+                achievements
+                    .unlocked
+                    .insert(achievement.id.to_string(), event.at);
+                achievement_unlocked_events.send(AchievementUnlockedEvent {
+                    id: achievement.id,
+                    at: event.at,
+                });
+            }
+        }
+    }
+}
@@ -0,0 +1,91 @@
+use super::{
+    ActiveQuest, CurrentQuestInfo, DeathRules, Health, QuestOutcome, QuestPool, QuestRecord,
+    QuestScheduler, QuestSelector, TreeInfo,
+};
+use bevy::prelude::*;
+use chrono::{DateTime, Duration, Utc};
+
+/// What happened to the tree while the game was closed.
+#[derive(Resource)]
+pub struct AwaySummary {
+    pub away_for: Duration,
+    pub missed_quests: Vec<String>,
+    pub health_before: Health,
+    pub health_after: Health,
+}
+
+/// Replays the quest lifecycle from the last save up to `now`, the way
+/// `check_deadline`, `check_next_quest` and `enqueue_recurring_quests` would have if
+/// the game had been running.
+/// Returns the records of the quests missed meanwhile.
+pub fn catch_up(
+    now: DateTime<Utc>,
+    current_quest_info: &mut CurrentQuestInfo,
+    quest_pool: &mut QuestPool,
+    tree: &mut TreeInfo,
+    death_rules: &DeathRules,
+    scheduler: &mut QuestScheduler,
+    selector: &mut QuestSelector,
+) -> Vec<QuestRecord> {
+    let mut missed_quests = Vec::new();
+    // Set once the pool has nothing left to offer, so only deadlines remain.
+    let mut pool_exhausted = false;
+    // Start of the latest recurring occurrence replayed, which its quest can't appear before.
+    let mut refilled_at = None;
+
+    while !tree.is_dead() {
+        let next_deadline = current_quest_info
+            .active_quests
+            .iter()
+            .enumerate()
+            .min_by_key(|(_, active_quest)| active_quest.deadline)
+            .map(|(index, active_quest)| (index, active_quest.deadline));
+        let next_appearance = (!pool_exhausted
+            && current_quest_info.active_quests.len() < scheduler.max_active)
+            .then(|| current_quest_info.next_quest_at(scheduler.cooldown))
+            .map(|appeared_at| refilled_at.map_or(appeared_at, |at| appeared_at.max(at)))
+            .filter(|appeared_at| *appeared_at < now);
+        let next_occurrence = scheduler.next_occurrence(now);
+
+        match (next_deadline, next_appearance, next_occurrence) {
+            (Some((index, deadline)), appearance, ref occurrence)
+                if deadline < now
+                    && appearance.is_none_or(|appeared_at| deadline <= appeared_at)
+                    && occurrence
+                        .as_ref()
+                        .is_none_or(|(_, start)| deadline <= *start) =>
+            {
+                let active_quest = current_quest_info.active_quests.remove(index);
+                let penalty = active_quest.quest.scaled_penalty();
+                missed_quests.push(QuestRecord::new(
+                    &active_quest,
+                    deadline,
+                    QuestOutcome::Missed,
+                ));
+                current_quest_info.last_quest_finished = deadline;
+                tree.miss_quest(deadline, penalty, death_rules);
+            }
+            (_, appearance, Some((quest, start)))
+                if appearance.is_none_or(|appeared_at| start <= appeared_at) =>
+            {
+                scheduler.enqueue(quest, start, quest_pool, current_quest_info);
+                refilled_at = Some(start);
+                pool_exhausted = false;
+            }
+            (_, Some(appeared_at), _) => {
+                quest_pool.wake_snoozed(appeared_at);
+                scheduler.drop_closed(appeared_at, quest_pool);
+                match selector.next(quest_pool, current_quest_info) {
+                    Some(quest) => current_quest_info
+                        .active_quests
+                        .push(ActiveQuest::new(quest, appeared_at)),
+                    None => pool_exhausted = true,
+                }
+            }
+            _ => break,
+        }
+    }
+    tree.decay(now);
+
+    missed_quests
+}
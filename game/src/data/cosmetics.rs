@@ -0,0 +1,201 @@
+//! Leaves earned from quests, and the cosmetics they buy.
+
+use super::{Difficulty, QuestCompletedEvent};
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+
+/// Leaves earned for a completed quest of `Difficulty::Medium`.
+pub const LEAVES_PER_QUEST: u32 = 10;
+
+/// Where a cosmetic goes. Only one cosmetic per slot is worn at a time.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum CosmeticSlot {
+    Pot,
+    Ground,
+    Decoration,
+    /// Tints the tree itself.
+    Skin,
+}
+
+pub struct Cosmetic {
+    /// Stable identifier the purchase is saved under.
+    pub id: &'static str,
+    pub name: &'static str,
+    pub slot: CosmeticSlot,
+    pub price: u32,
+    pub color: (f32, f32, f32),
+}
+
+pub static COSMETICS: [Cosmetic; 8] = [
+    Cosmetic {
+        id: "clay_pot",
+        name: "Clay pot",
+        slot: CosmeticSlot::Pot,
+        price: 30,
+        color: (0.72, 0.4, 0.25),
+    },
+    Cosmetic {
+        id: "glazed_pot",
+        name: "Glazed pot",
+        slot: CosmeticSlot::Pot,
+        price: 80,
+        color: (0.25, 0.45, 0.7),
+    },
+    Cosmetic {
+        id: "moss",
+        name: "Moss",
+        slot: CosmeticSlot::Ground,
+        price: 40,
+        color: (0.35, 0.55, 0.25),
+    },
+    Cosmetic {
+        id: "pebbles",
+        name: "Pebbles",
+        slot: CosmeticSlot::Ground,
+        price: 60,
+        color: (0.6, 0.6, 0.6),
+    },
+    Cosmetic {
+        id: "mushroom",
+        name: "Mushroom",
+        slot: CosmeticSlot::Decoration,
+        price: 50,
+        color: (0.85, 0.2, 0.2),
+    },
+    Cosmetic {
+        id: "lantern",
+        name: "Lantern",
+        slot: CosmeticSlot::Decoration,
+        price: 120,
+        color: (1.0, 0.85, 0.4),
+    },
+    Cosmetic {
+        id: "autumn_skin",
+        name: "Autumn leaves",
+        slot: CosmeticSlot::Skin,
+        price: 100,
+        color: (1.0, 0.75, 0.5),
+    },
+    Cosmetic {
+        id: "frost_skin",
+        name: "Frost",
+        slot: CosmeticSlot::Skin,
+        price: 150,
+        color: (0.8, 0.9, 1.0),
+    },
+];
+
+pub fn cosmetic(id: &str) -> Option<&'static Cosmetic> {
+    COSMETICS.iter().find(|cosmetic| cosmetic.id == id)
+}
+
+/// Leaves earned for completing a quest of `difficulty`.
+pub fn leaves_for(difficulty: Difficulty) -> u32 {
+    (LEAVES_PER_QUEST as f32 * difficulty.multiplier()).round() as u32
+}
+
+#[derive(Resource, Clone, Default, Serialize, Deserialize)]
+pub struct Wallet {
+    pub leaves: u32,
+}
+
+/// Cosmetics bought so far, and the ones worn, by id.
+#[derive(Resource, Clone, Default, Serialize, Deserialize)]
+pub struct Wardrobe {
+    pub owned: HashSet<String>,
+    pub equipped: HashMap<CosmeticSlot, String>,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum PurchaseError {
+    UnknownCosmetic,
+    AlreadyOwned,
+    NotEnoughLeaves,
+}
+
+impl fmt::Display for PurchaseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PurchaseError::UnknownCosmetic => write!(f, "no such cosmetic"),
+            PurchaseError::AlreadyOwned => write!(f, "cosmetic is already owned"),
+            PurchaseError::NotEnoughLeaves => write!(f, "not enough leaves"),
+        }
+    }
+}
+
+impl Wardrobe {
+    pub fn owns(&self, id: &str) -> bool {
+        self.owned.contains(id)
+    }
+
+    pub fn equipped(&self, slot: CosmeticSlot) -> Option<&'static Cosmetic> {
+        self.equipped.get(&slot).and_then(|id| cosmetic(id))
+    }
+
+    pub fn is_equipped(&self, cosmetic: &Cosmetic) -> bool {
+        self.equipped.get(&cosmetic.slot).map(String::as_str) == Some(cosmetic.id)
+    }
+
+    /// Pays for the cosmetic with `id` out of `wallet` and puts it on.
+    pub fn buy(&mut self, id: &str, wallet: &mut Wallet) -> Result<(), PurchaseError> {
+        let cosmetic = cosmetic(id).ok_or(PurchaseError::UnknownCosmetic)?;
+        if self.owns(id) {
+            return Err(PurchaseError::AlreadyOwned);
+        }
+        wallet.leaves = wallet
+            .leaves
+            .checked_sub(cosmetic.price)
+            .ok_or(PurchaseError::NotEnoughLeaves)?;
+        self.owned.insert(id.to_string());
+        self.equipped.insert(cosmetic.slot, cosmetic.id.to_string());
+        Ok(())
+    }
+
+    /// Puts on an owned cosmetic, or takes it off if it is already worn.
+    pub fn toggle(&mut self, id: &str) {
+        let Some(cosmetic) = cosmetic(id).filter(|_| self.owns(id)) else {
+            return;
+        };
+        if self.is_equipped(cosmetic) {
+            self.equipped.remove(&cosmetic.slot);
+        } else {
+            self.equipped.insert(cosmetic.slot, cosmetic.id.to_string());
+        }
+    }
+}
+
+pub struct BuyCosmeticEvent {
+    pub id: String,
+}
+
+/// Puts on an owned cosmetic, or takes it off.
+pub struct ToggleCosmeticEvent {
+    pub id: String,
+}
+
+pub(crate) fn earn_leaves(
+    mut quest_completed_events: EventReader<QuestCompletedEvent>,
+    mut wallet: ResMut<Wallet>,
+) {
+    for event in quest_completed_events.iter() {
+        wallet.leaves += leaves_for(event.difficulty);
+    }
+}
+
+pub(crate) fn handle_cosmetic_events(
+    mut buy_cosmetic_events: EventReader<BuyCosmeticEvent>,
+    mut toggle_cosmetic_events: EventReader<ToggleCosmeticEvent>,
+    mut wardrobe: ResMut<Wardrobe>,
+    mut wallet: ResMut<Wallet>,
+) {
+    for event in buy_cosmetic_events.iter() {
+        if let Err(err) = wardrobe.buy(&event.id, &mut wallet) {
+            warn!("Can't buy {}: {}", event.id, err);
+        }
+    }
+    for event in toggle_cosmetic_events.iter() {
+        wardrobe.toggle(&event.id);
+    }
+}
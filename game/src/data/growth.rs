@@ -0,0 +1,67 @@
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Growth points needed to reach every stage after the sapling.
+pub const GROWTH_STAGE_POINTS: [u32; 2] = [3, 8];
+
+/// Quests missed in a row before the tree starts losing growth.
+pub const NEGLECT_STUNT_STREAK: u32 = 3;
+
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Growth {
+    /// Unknown for trees saved before growth was tracked.
+    pub planted_at: Option<DateTime<Utc>>,
+    pub points: u32,
+    /// 0 is a sapling, `GROWTH_STAGE_POINTS.len()` is fully grown.
+    pub stage: usize,
+    /// Quests missed since the last completed one.
+    pub neglect: u32,
+}
+
+impl Growth {
+    pub fn planted(now: DateTime<Utc>) -> Self {
+        Growth {
+            planted_at: Some(now),
+            ..Default::default()
+        }
+    }
+
+    pub fn age(&self, now: DateTime<Utc>) -> Duration {
+        self.planted_at
+            .map_or_else(Duration::zero, |planted_at| now - planted_at)
+    }
+
+    pub fn is_fully_grown(&self) -> bool {
+        self.stage >= GROWTH_STAGE_POINTS.len()
+    }
+
+    /// Adds growth points for a completed quest. Returns whether a new stage was reached.
+    pub fn grow(&mut self, points: u32) -> bool {
+        self.neglect = 0;
+        self.points += points;
+        let stage = stage_for_points(self.points);
+        let reached = stage > self.stage;
+        self.stage = self.stage.max(stage);
+        reached
+    }
+
+    /// Records a missed quest. Once neglect is sustained the tree loses growth,
+    /// but never enough to fall back a stage.
+    pub fn neglect(&mut self) {
+        self.neglect += 1;
+        if self.neglect >= NEGLECT_STUNT_STREAK {
+            let stage_floor = match self.stage {
+                0 => 0,
+                stage => GROWTH_STAGE_POINTS[stage - 1],
+            };
+            self.points = self.points.saturating_sub(1).max(stage_floor);
+        }
+    }
+}
+
+fn stage_for_points(points: u32) -> usize {
+    GROWTH_STAGE_POINTS
+        .iter()
+        .take_while(|threshold| points >= **threshold)
+        .count()
+}
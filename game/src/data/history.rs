@@ -0,0 +1,146 @@
+use super::{ActiveQuest, QuestCategory};
+use bevy::prelude::*;
+use chrono::{DateTime, Duration, NaiveDate, TimeZone, Utc};
+use serde::{Deserialize, Serialize};
+use std::cmp::Reverse;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum QuestOutcome {
+    Completed,
+    Missed,
+    Skipped,
+    Rejected,
+}
+
+/// A quest that was on offer, and how it ended.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct QuestRecord {
+    pub quest_id: String,
+    pub name: String,
+    pub category: QuestCategory,
+    pub appeared_at: DateTime<Utc>,
+    pub finished_at: DateTime<Utc>,
+    pub outcome: QuestOutcome,
+}
+
+impl QuestRecord {
+    pub fn new(
+        active_quest: &ActiveQuest,
+        finished_at: DateTime<Utc>,
+        outcome: QuestOutcome,
+    ) -> Self {
+        QuestRecord {
+            quest_id: active_quest.quest.id.clone(),
+            name: active_quest.quest.name.clone(),
+            category: active_quest.quest.category,
+            appeared_at: active_quest.appeared_at,
+            finished_at,
+            outcome,
+        }
+    }
+
+    pub fn time_taken(&self) -> Duration {
+        self.finished_at - self.appeared_at
+    }
+}
+
+/// Every finished quest, oldest first. Snoozed quests aren't finished, they come back.
+#[derive(Resource, Clone, Default, Serialize, Deserialize)]
+pub struct QuestHistory {
+    pub records: Vec<QuestRecord>,
+}
+
+impl QuestHistory {
+    pub fn record(
+        &mut self,
+        active_quest: &ActiveQuest,
+        finished_at: DateTime<Utc>,
+        outcome: QuestOutcome,
+    ) {
+        self.records
+            .push(QuestRecord::new(active_quest, finished_at, outcome));
+    }
+
+    pub fn completed(&self) -> impl Iterator<Item = &QuestRecord> {
+        self.records
+            .iter()
+            .filter(|record| record.outcome == QuestOutcome::Completed)
+    }
+
+    /// Outcomes that count towards the stats. A rejected quest was never going to be done.
+    fn counted_outcomes(&self) -> impl Iterator<Item = QuestOutcome> + '_ {
+        self.records
+            .iter()
+            .map(|record| record.outcome)
+            .filter(|outcome| *outcome != QuestOutcome::Rejected)
+    }
+
+    /// Share of the counted quests that were completed, if there are any yet.
+    pub fn completion_rate(&self) -> Option<f32> {
+        let counted = self.counted_outcomes().count();
+        (counted > 0).then(|| self.completed().count() as f32 / counted as f32)
+    }
+
+    /// Categories by completions, most completed first.
+    pub fn best_categories(&self) -> Vec<(QuestCategory, u32)> {
+        let mut categories: Vec<(QuestCategory, u32)> = Vec::new();
+        for record in self.completed() {
+            match categories
+                .iter_mut()
+                .find(|(category, _)| *category == record.category)
+            {
+                Some((_, completions)) => *completions += 1,
+                None => categories.push((record.category, 1)),
+            }
+        }
+        categories.sort_by_key(|(_, completions)| Reverse(*completions));
+        categories
+    }
+
+    /// Most days in a row with a completed quest, in `time_zone`.
+    pub fn longest_day_streak<Tz: TimeZone>(&self, time_zone: Tz) -> u32 {
+        let mut days: Vec<NaiveDate> = self
+            .completed()
+            .map(|record| record.finished_at.with_timezone(&time_zone).date_naive())
+            .collect();
+        // Completions are recorded in order, so a day's completions sit together.
+        days.dedup();
+
+        let mut longest = 0;
+        let mut streak = 0;
+        let mut previous_day: Option<NaiveDate> = None;
+        for day in days {
+            streak = match previous_day.and_then(|previous_day| previous_day.succ_opt()) {
+                Some(next_day) if next_day == day => streak + 1,
+                _ => 1,
+            };
+            longest = longest.max(streak);
+            previous_day = Some(day);
+        }
+        longest
+    }
+
+    pub fn average_time_taken(&self) -> Option<Duration> {
+        let completed = self.completed().count() as i32;
+        (completed > 0).then(|| {
+            self.completed()
+                .map(QuestRecord::time_taken)
+                .fold(Duration::zero(), |total, time_taken| total + time_taken)
+                / completed
+        })
+    }
+
+    /// Completions in each of the last `weeks` seven-day spans up to `now`, oldest first.
+    pub fn weekly_completions(&self, now: DateTime<Utc>, weeks: usize) -> Vec<u32> {
+        let mut completions = vec![0; weeks];
+        for record in self.completed() {
+            let Ok(weeks_ago) = usize::try_from((now - record.finished_at).num_weeks()) else {
+                continue;
+            };
+            if weeks_ago < weeks {
+                completions[weeks - 1 - weeks_ago] += 1;
+            }
+        }
+        completions
+    }
+}
@@ -0,0 +1,162 @@
+//! Experience the player earns across all trees, and what each level unlocks.
+
+use super::{Difficulty, QuestCategory, QuestCompletedEvent, QuestPool, TreeKind};
+use bevy::prelude::*;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// XP earned for a completed quest of `Difficulty::Medium`.
+pub const XP_PER_QUEST: u32 = 20;
+
+/// XP earned whenever a tree grows into its next stage.
+pub const XP_PER_STAGE: u32 = 50;
+
+/// XP it takes to get from level 1 to level 2. Every level after takes this much more.
+pub const LEVEL_XP: u32 = 100;
+
+/// Tree kinds every player starts out with, in the order they are planted.
+const STARTING_TREE_KINDS: [TreeKind; 2] = [TreeKind::Oak, TreeKind::Birch];
+
+/// Something a level gives access to.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Unlock {
+    TreeKind(TreeKind),
+    /// Quests of the category are held back until then.
+    QuestCategory(QuestCategory),
+}
+
+pub struct LevelUnlock {
+    pub level: u32,
+    pub unlock: Unlock,
+}
+
+/// Everything not listed is available from level 1.
+pub static UNLOCKS: [LevelUnlock; 4] = [
+    LevelUnlock {
+        level: 2,
+        unlock: Unlock::TreeKind(TreeKind::Pine),
+    },
+    LevelUnlock {
+        level: 3,
+        unlock: Unlock::QuestCategory(QuestCategory::Creative),
+    },
+    LevelUnlock {
+        level: 4,
+        unlock: Unlock::TreeKind(TreeKind::Cherry),
+    },
+    LevelUnlock {
+        level: 5,
+        unlock: Unlock::QuestCategory(QuestCategory::Adventure),
+    },
+];
+
+/// Level `unlock` becomes available at.
+pub fn unlock_level(unlock: Unlock) -> u32 {
+    UNLOCKS
+        .iter()
+        .find(|level_unlock| level_unlock.unlock == unlock)
+        .map_or(1, |level_unlock| level_unlock.level)
+}
+
+/// What reaching `level` unlocks.
+pub fn unlocks_at(level: u32) -> impl Iterator<Item = Unlock> {
+    UNLOCKS
+        .iter()
+        .filter(move |level_unlock| level_unlock.level == level)
+        .map(|level_unlock| level_unlock.unlock)
+}
+
+/// Total XP it takes to reach `level`.
+pub fn level_xp(level: u32) -> u32 {
+    let level = level.max(1);
+    LEVEL_XP * level * (level - 1) / 2
+}
+
+/// XP earned for completing a quest of `difficulty`.
+pub fn xp_for(difficulty: Difficulty) -> u32 {
+    (XP_PER_QUEST as f32 * difficulty.multiplier()).round() as u32
+}
+
+#[derive(Resource, Clone, Default, Serialize, Deserialize)]
+pub struct Profile {
+    pub xp: u32,
+}
+
+impl Profile {
+    pub fn level(&self) -> u32 {
+        let mut level = 1;
+        while self.xp >= level_xp(level + 1) {
+            level += 1;
+        }
+        level
+    }
+
+    /// XP earned into the current level, and the XP the whole level takes.
+    pub fn level_progress(&self) -> (u32, u32) {
+        let level = self.level();
+        (
+            self.xp - level_xp(level),
+            level_xp(level + 1) - level_xp(level),
+        )
+    }
+
+    pub fn has_unlocked(&self, unlock: Unlock) -> bool {
+        self.level() >= unlock_level(unlock)
+    }
+
+    /// Tree kinds that can be planted, in the order plots take them.
+    pub fn tree_kinds(&self) -> Vec<TreeKind> {
+        STARTING_TREE_KINDS
+            .into_iter()
+            .chain(
+                UNLOCKS
+                    .iter()
+                    .filter_map(|level_unlock| match level_unlock.unlock {
+                        Unlock::TreeKind(kind) if self.level() >= level_unlock.level => Some(kind),
+                        _ => None,
+                    }),
+            )
+            .collect()
+    }
+
+    /// Kind of a tree planted on `plot`.
+    pub fn tree_kind_for(&self, plot: usize) -> TreeKind {
+        let kinds = self.tree_kinds();
+        kinds[plot % kinds.len()]
+    }
+}
+
+pub struct LevelUpEvent {
+    pub level: u32,
+    pub at: DateTime<Utc>,
+}
+
+/// Earns XP from completed quests and the growth they bring.
+pub(crate) fn gain_xp(
+    mut quest_completed_events: EventReader<QuestCompletedEvent>,
+    mut level_up_events: EventWriter<LevelUpEvent>,
+    mut profile: ResMut<Profile>,
+) {
+    for event in quest_completed_events.iter() {
+        let level_before = profile.level();
+        profile.xp += xp_for(event.difficulty);
+        if event.new_stage.is_some() {
+            profile.xp += XP_PER_STAGE;
+        }
+        for level in level_before + 1..=profile.level() {
+            level_up_events.send(LevelUpEvent {
+                level,
+                at: event.at,
+            });
+        }
+    }
+}
+
+/// Keeps quests of locked categories out of the queue, and queues them once unlocked.
+pub(crate) fn hold_locked_quests(mut quest_pool: ResMut<QuestPool>, profile: Res<Profile>) {
+    // Only touched when something moves, so the pool isn't saved every frame.
+    if quest_pool.is_held_for(&profile) {
+        return;
+    }
+    quest_pool.hold_for(&profile);
+}
@@ -0,0 +1,182 @@
+use super::{CurrentQuestInfo, Quest, QuestPool, QuestScheduler};
+use bevy::asset::{AssetLoader, LoadContext, LoadState, LoadedAsset};
+use bevy::prelude::*;
+use bevy::reflect::TypeUuid;
+use bevy::utils::BoxedFuture;
+use serde::Deserialize;
+use std::collections::HashMap;
+
+/// Quest files loaded on startup, in the order their quests are queued.
+#[derive(Resource)]
+pub struct QuestFiles(pub Vec<String>);
+
+impl Default for QuestFiles {
+    fn default() -> Self {
+        QuestFiles(vec![
+            "quests/default.quests.ron".to_string(),
+            "quests/recurring.quests.ron".to_string(),
+        ])
+    }
+}
+
+#[derive(Deserialize, TypeUuid)]
+#[uuid = "5b7f3a53-0c1e-4d0b-9a67-2f6c3d1c8e41"]
+pub struct QuestList {
+    pub quests: Vec<Quest>,
+}
+
+/// Loads `*.quests.ron` and `*.quests.json` files into a [`QuestList`].
+#[derive(Default)]
+pub struct QuestListLoader;
+
+impl AssetLoader for QuestListLoader {
+    fn load<'a>(
+        &'a self,
+        bytes: &'a [u8],
+        load_context: &'a mut LoadContext,
+    ) -> BoxedFuture<'a, Result<(), bevy::asset::Error>> {
+        Box::pin(async move {
+            let is_json = load_context
+                .path()
+                .extension()
+                .is_some_and(|extension| extension == "json");
+            let quest_list: QuestList = if is_json {
+                serde_json::from_slice(bytes)?
+            } else {
+                ron::de::from_bytes(bytes)?
+            };
+            load_context.set_default_asset(LoadedAsset::new(quest_list));
+            Ok(())
+        })
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["quests.ron", "quests.json"]
+    }
+}
+
+#[derive(Resource, Default)]
+pub struct QuestAssets {
+    pub handles: Vec<Handle<QuestList>>,
+}
+
+/// Every quest in the quest files, which saved quests are looked up in by id.
+#[derive(Resource, Default)]
+pub struct QuestCatalog {
+    /// Quests by id, follow-ups included.
+    pub quests: HashMap<String, Quest>,
+    /// Ids of the one-shot quests the pool starts out with, in file order.
+    pub one_shot: Vec<String>,
+    /// Set once the quest files have loaded, or failed to.
+    pub loaded: bool,
+}
+
+impl QuestCatalog {
+    fn insert(&mut self, quest: &Quest) {
+        self.quests.insert(quest.id.clone(), quest.clone());
+        quest
+            .follow_ups
+            .iter()
+            .for_each(|follow_up| self.insert(follow_up));
+    }
+
+    /// Swaps the placeholder quests of a restored save for their current definitions,
+    /// dropping the ones that have since been taken out of the quest files.
+    pub fn resolve(&self, current_quest_info: &mut CurrentQuestInfo, quest_pool: &mut QuestPool) {
+        let lookup = |quest: &Quest| self.quests.get(&quest.id).cloned();
+        current_quest_info.active_quests.retain_mut(|active_quest| {
+            let Some(quest) = lookup(&active_quest.quest) else {
+                return false;
+            };
+            active_quest.progress = active_quest.progress.min(quest.target());
+            active_quest.quest = quest;
+            true
+        });
+        quest_pool.queue = quest_pool.queue.iter().filter_map(lookup).collect();
+        quest_pool.locked = quest_pool.locked.iter().filter_map(lookup).collect();
+        quest_pool.snoozed.retain_mut(|snoozed_quest| {
+            let Some(quest) = lookup(&snoozed_quest.quest) else {
+                return false;
+            };
+            snoozed_quest.quest = quest;
+            true
+        });
+    }
+}
+
+pub fn load_quest_assets(
+    asset_server: Res<AssetServer>,
+    quest_files: Res<QuestFiles>,
+    mut quest_assets: ResMut<QuestAssets>,
+) {
+    quest_assets.handles = quest_files
+        .0
+        .iter()
+        .map(|path| asset_server.load(path.as_str()))
+        .collect();
+}
+
+/// Fills the catalog and the recurring quests of the scheduler once the quest files have loaded.
+pub fn load_quest_catalog(
+    asset_server: Res<AssetServer>,
+    quest_lists: Res<Assets<QuestList>>,
+    quest_assets: Res<QuestAssets>,
+    mut catalog: ResMut<QuestCatalog>,
+    mut scheduler: ResMut<QuestScheduler>,
+) {
+    if catalog.loaded {
+        return;
+    }
+
+    let still_loading = quest_assets.handles.iter().any(|handle| {
+        !matches!(
+            asset_server.get_load_state(handle),
+            LoadState::Loaded | LoadState::Failed
+        )
+    });
+    if still_loading {
+        return;
+    }
+
+    for handle in quest_assets.handles.iter() {
+        let path = asset_server
+            .get_handle_path(handle)
+            .map(|path| path.path().display().to_string())
+            .unwrap_or_default();
+        let Some(quest_list) = quest_lists.get(handle) else {
+            error!("Failed to load quest file {:?}", path);
+            continue;
+        };
+
+        for (index, quest) in quest_list.quests.iter().enumerate() {
+            if let Err(err) = quest.validate() {
+                error!("Skipping quest #{} in {:?}: {}", index, path, err);
+                continue;
+            }
+            let mut quest = quest.clone();
+            quest.fill_ids();
+            catalog.insert(&quest);
+            if quest.schedule.is_some() {
+                scheduler.recurring.push(quest);
+            } else {
+                catalog.one_shot.push(quest.id);
+            }
+        }
+    }
+
+    catalog.loaded = true;
+}
+
+/// Queues the one-shot quests of the catalog that haven't been queued before.
+pub fn fill_quest_pool(catalog: Res<QuestCatalog>, mut quest_pool: ResMut<QuestPool>) {
+    if !catalog.is_changed() {
+        return;
+    }
+    for id in &catalog.one_shot {
+        if quest_pool.introduced.contains(id) {
+            continue;
+        }
+        quest_pool.introduced.insert(id.clone());
+        quest_pool.queue.push_back(catalog.quests[id].clone());
+    }
+}
@@ -0,0 +1,43 @@
+//! (De)serializes a `Quest` as just its id, so a save game doesn't hold on to quest
+//! definitions. The quest comes back as a placeholder holding only the id, until it is
+//! looked up in the `QuestCatalog` once the quest files have loaded.
+
+use super::Quest;
+use serde::{Deserialize, Deserializer, Serializer};
+
+fn placeholder(id: String) -> Quest {
+    Quest {
+        id,
+        ..Default::default()
+    }
+}
+
+pub fn serialize<S: Serializer>(quest: &Quest, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.serialize_str(&quest.id)
+}
+
+pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Quest, D::Error> {
+    String::deserialize(deserializer).map(placeholder)
+}
+
+/// The same for a sequence of quests.
+pub mod seq {
+    use super::{placeholder, Quest};
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<'a, S: Serializer>(
+        quests: impl IntoIterator<Item = &'a Quest>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(quests.into_iter().map(|quest| &quest.id))
+    }
+
+    pub fn deserialize<'de, D, C>(deserializer: D) -> Result<C, D::Error>
+    where
+        D: Deserializer<'de>,
+        C: FromIterator<Quest>,
+    {
+        let ids = Vec::<String>::deserialize(deserializer)?;
+        Ok(ids.into_iter().map(placeholder).collect())
+    }
+}
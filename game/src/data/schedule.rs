@@ -0,0 +1,295 @@
+//! Recurring quests that come back into the pool on a schedule.
+
+use super::{CurrentQuestInfo, Quest, QuestPool};
+use crate::clock::GameClock;
+use bevy::prelude::*;
+use chrono::{
+    DateTime, Datelike, Duration, FixedOffset, Local, LocalResult, NaiveDate, NaiveDateTime,
+    NaiveTime, TimeZone, Utc, Weekday,
+};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Default rest between one quest finishing or appearing and the next one appearing.
+pub const DEFAULT_QUEST_COOLDOWN_SECS: i64 = 5;
+
+/// Default number of quests on offer at the same time.
+pub const DEFAULT_MAX_ACTIVE_QUESTS: usize = 3;
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ScheduleDays {
+    Daily,
+    /// Monday to Friday.
+    Weekdays,
+    Weekly(Weekday),
+}
+
+impl ScheduleDays {
+    fn includes(&self, weekday: Weekday) -> bool {
+        match self {
+            ScheduleDays::Daily => true,
+            ScheduleDays::Weekdays => weekday.number_from_monday() <= 5,
+            ScheduleDays::Weekly(day) => *day == weekday,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Schedule {
+    pub days: ScheduleDays,
+    /// Local time of day the quest comes up, like `"08:00:00"`.
+    pub at: NaiveTime,
+    /// End of the time-of-day window, on the next day if it isn't after `at`. An
+    /// occurrence that could not be offered by then is skipped; without it the quest
+    /// stays available until the next one.
+    #[serde(default)]
+    pub until: Option<NaiveTime>,
+}
+
+impl Schedule {
+    /// Start of the occurrence on `date`, if the schedule has one that day. The offset
+    /// is the one in effect at that time, which daylight saving time shifts.
+    fn start_on<Tz: TimeZone>(&self, date: NaiveDate, time_zone: &Tz) -> Option<DateTime<Utc>> {
+        if !self.days.includes(date.weekday()) {
+            return None;
+        }
+        time_zone
+            .from_local_datetime(&date.and_time(self.at))
+            .earliest()
+            .map(|start| start.with_timezone(&Utc))
+    }
+
+    /// End of the window of the occurrence starting at `start`, if the schedule has one.
+    pub fn window_end<Tz: TimeZone>(
+        &self,
+        start: DateTime<Utc>,
+        time_zone: Tz,
+    ) -> Option<DateTime<Utc>> {
+        let until = self.until?;
+        let local_start = start.with_timezone(&time_zone).naive_local();
+        // A window that ends at or before the time it starts runs past midnight.
+        let overnight = until <= self.at;
+        let local_end = (local_start.date() + Duration::days(overnight as i64)).and_time(until);
+        let end = time_zone
+            .from_local_datetime(&local_end)
+            .earliest()
+            .map_or(start + (local_end - local_start), |end| {
+                end.with_timezone(&Utc)
+            });
+        Some(end)
+    }
+
+    /// Start of the latest occurrence at or before `now`, if its window is still open.
+    pub fn open_occurrence<Tz: TimeZone>(
+        &self,
+        now: DateTime<Utc>,
+        time_zone: Tz,
+    ) -> Option<DateTime<Utc>> {
+        let today = now.with_timezone(&time_zone).date_naive();
+        // Weekly schedules repeat at most a week apart.
+        let start = (0..=7)
+            .filter_map(|days_back| self.start_on(today - Duration::days(days_back), &time_zone))
+            .find(|start| *start <= now)?;
+
+        match self.window_end(start, time_zone) {
+            Some(end) if now > end => None,
+            _ => Some(start),
+        }
+    }
+
+    /// Start of the first occurrence after `after`.
+    pub fn next_start<Tz: TimeZone>(
+        &self,
+        after: DateTime<Utc>,
+        time_zone: Tz,
+    ) -> Option<DateTime<Utc>> {
+        let day = after.with_timezone(&time_zone).date_naive();
+        (0..=8)
+            .filter_map(|days_ahead| self.start_on(day + Duration::days(days_ahead), &time_zone))
+            .find(|start| *start > after)
+    }
+}
+
+/// Time zone the schedules are written in.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ScheduleTimeZone {
+    /// The one the device is set to, along with its daylight saving time.
+    Local,
+    Fixed(FixedOffset),
+}
+
+impl TimeZone for ScheduleTimeZone {
+    type Offset = FixedOffset;
+
+    fn from_offset(offset: &FixedOffset) -> Self {
+        ScheduleTimeZone::Fixed(*offset)
+    }
+
+    fn offset_from_local_date(&self, local: &NaiveDate) -> LocalResult<FixedOffset> {
+        match self {
+            ScheduleTimeZone::Local => Local.offset_from_local_date(local),
+            ScheduleTimeZone::Fixed(offset) => offset.offset_from_local_date(local),
+        }
+    }
+
+    fn offset_from_local_datetime(&self, local: &NaiveDateTime) -> LocalResult<FixedOffset> {
+        match self {
+            ScheduleTimeZone::Local => Local.offset_from_local_datetime(local),
+            ScheduleTimeZone::Fixed(offset) => offset.offset_from_local_datetime(local),
+        }
+    }
+
+    fn offset_from_utc_date(&self, utc: &NaiveDate) -> FixedOffset {
+        match self {
+            ScheduleTimeZone::Local => Local.offset_from_utc_date(utc),
+            ScheduleTimeZone::Fixed(offset) => offset.offset_from_utc_date(utc),
+        }
+    }
+
+    fn offset_from_utc_datetime(&self, utc: &NaiveDateTime) -> FixedOffset {
+        match self {
+            ScheduleTimeZone::Local => Local.offset_from_utc_datetime(utc),
+            ScheduleTimeZone::Fixed(offset) => offset.offset_from_utc_datetime(utc),
+        }
+    }
+}
+
+/// Decides when quests come up: recurring quests are re-enqueued on their
+/// schedule, and every quest waits out the cooldown after the previous one.
+#[derive(Resource, Clone, Serialize, Deserialize)]
+pub struct QuestScheduler {
+    /// Loaded from the quest files on every start rather than saved.
+    #[serde(skip)]
+    pub recurring: Vec<Quest>,
+    /// Start of the occurrence each recurring quest was last enqueued for, by quest id.
+    #[serde(default)]
+    pub last_enqueued: HashMap<String, DateTime<Utc>>,
+    #[serde(skip, default = "default_cooldown")]
+    pub cooldown: Duration,
+    /// How many quests can be active at once.
+    #[serde(skip, default = "default_max_active")]
+    pub max_active: usize,
+    #[serde(skip, default = "local_time_zone")]
+    pub time_zone: ScheduleTimeZone,
+}
+
+fn default_cooldown() -> Duration {
+    Duration::seconds(DEFAULT_QUEST_COOLDOWN_SECS)
+}
+
+fn default_max_active() -> usize {
+    DEFAULT_MAX_ACTIVE_QUESTS
+}
+
+fn local_time_zone() -> ScheduleTimeZone {
+    ScheduleTimeZone::Local
+}
+
+impl Default for QuestScheduler {
+    fn default() -> Self {
+        QuestScheduler {
+            recurring: Vec::new(),
+            last_enqueued: HashMap::new(),
+            cooldown: default_cooldown(),
+            max_active: default_max_active(),
+            time_zone: local_time_zone(),
+        }
+    }
+}
+
+impl QuestScheduler {
+    /// Recurring quests whose current occurrence hasn't been enqueued yet.
+    fn due(&self, now: DateTime<Utc>) -> Vec<(Quest, DateTime<Utc>)> {
+        self.recurring
+            .iter()
+            .filter_map(|quest| {
+                let occurrence = quest
+                    .schedule
+                    .as_ref()?
+                    .open_occurrence(now, self.time_zone)?;
+                let enqueued = self
+                    .last_enqueued
+                    .get(&quest.id)
+                    .is_some_and(|last_enqueued| *last_enqueued >= occurrence);
+                (!enqueued).then(|| (quest.clone(), occurrence))
+            })
+            .collect()
+    }
+
+    /// The earliest occurrence up to `now` that follows the last enqueued one of its
+    /// quest. Quests never enqueued before have nothing to follow.
+    pub(crate) fn next_occurrence(&self, now: DateTime<Utc>) -> Option<(Quest, DateTime<Utc>)> {
+        self.recurring
+            .iter()
+            .filter_map(|quest| {
+                let last_enqueued = self.last_enqueued.get(&quest.id)?;
+                let start = quest
+                    .schedule
+                    .as_ref()?
+                    .next_start(*last_enqueued, self.time_zone)?;
+                (start <= now).then_some((quest, start))
+            })
+            .min_by_key(|(_, start)| *start)
+            .map(|(quest, start)| (quest.clone(), start))
+    }
+
+    /// Whether the window of the occurrence `quest` was last enqueued for closed before `now`.
+    pub(crate) fn window_closed(&self, quest: &Quest, now: DateTime<Utc>) -> bool {
+        let (Some(schedule), Some(start)) = (&quest.schedule, self.last_enqueued.get(&quest.id))
+        else {
+            return false;
+        };
+        schedule
+            .window_end(*start, self.time_zone)
+            .is_some_and(|end| now > end)
+    }
+
+    /// Takes the recurring quests out of the queue whose window closed before they
+    /// could be offered.
+    pub(crate) fn drop_closed(&self, now: DateTime<Utc>, quest_pool: &mut QuestPool) {
+        quest_pool
+            .queue
+            .retain(|quest| !self.window_closed(quest, now));
+    }
+
+    /// Records `occurrence` of `quest` as enqueued, and queues the quest unless it is
+    /// still waiting from an earlier occurrence or has been rejected.
+    pub(crate) fn enqueue(
+        &mut self,
+        quest: Quest,
+        occurrence: DateTime<Utc>,
+        quest_pool: &mut QuestPool,
+        current_quest_info: &CurrentQuestInfo,
+    ) {
+        let pending = quest_pool.queue.iter().any(|queued| queued.id == quest.id)
+            || quest_pool
+                .snoozed
+                .iter()
+                .any(|snoozed_quest| snoozed_quest.quest.id == quest.id)
+            || current_quest_info.get(&quest.id).is_some();
+        self.last_enqueued.insert(quest.id.clone(), occurrence);
+        if !pending && !quest_pool.rejected.contains(&quest.id) {
+            quest_pool.queue.push_back(quest);
+        }
+    }
+}
+
+pub fn enqueue_recurring_quests(
+    mut scheduler: ResMut<QuestScheduler>,
+    mut quest_pool: ResMut<QuestPool>,
+    current_quest_info: Res<CurrentQuestInfo>,
+    clock: Res<GameClock>,
+) {
+    let now = clock.now();
+    for (quest, occurrence) in scheduler.due(now) {
+        scheduler.enqueue(quest, occurrence, &mut quest_pool, &current_quest_info);
+    }
+    // Checked first, so an idle pool isn't marked as changed.
+    if quest_pool
+        .queue
+        .iter()
+        .any(|quest| scheduler.window_closed(quest, now))
+    {
+        scheduler.drop_closed(now, &mut quest_pool);
+    }
+}
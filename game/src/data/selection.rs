@@ -0,0 +1,169 @@
+//! Strategies for picking the next quest out of the pool.
+
+use super::{CurrentQuestInfo, Difficulty, Quest, QuestCategory, QuestPool};
+use bevy::ecs::system::SystemParam;
+use bevy::prelude::*;
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+use std::collections::{HashMap, VecDeque};
+use std::marker::PhantomData;
+
+/// Quests picked this recently aren't picked again while others are waiting.
+pub const DEFAULT_HISTORY_LEN: usize = 2;
+
+pub trait SelectionStrategy: Send + Sync {
+    /// Index into `candidates` of the quest to offer next, or `None` to offer nothing.
+    fn select(&mut self, candidates: &[&Quest]) -> Option<usize>;
+}
+
+/// Offers quests in the order they were queued.
+pub struct Fifo;
+
+impl SelectionStrategy for Fifo {
+    fn select(&mut self, candidates: &[&Quest]) -> Option<usize> {
+        (!candidates.is_empty()).then_some(0)
+    }
+}
+
+/// Picks quests at random, weighted by category and difficulty, skipping the
+/// ones picked most recently.
+pub struct WeightedRandom {
+    rng: ChaCha8Rng,
+    /// Categories and difficulties missing from the tables weigh 1.
+    pub category_weights: HashMap<QuestCategory, f32>,
+    pub difficulty_weights: HashMap<Difficulty, f32>,
+    pub history_len: usize,
+    history: VecDeque<String>,
+}
+
+impl WeightedRandom {
+    /// The same seed always picks the same quests from the same pool.
+    pub fn new(seed: u64) -> Self {
+        WeightedRandom {
+            rng: ChaCha8Rng::seed_from_u64(seed),
+            category_weights: HashMap::new(),
+            difficulty_weights: HashMap::new(),
+            history_len: DEFAULT_HISTORY_LEN,
+            history: VecDeque::new(),
+        }
+    }
+
+    pub fn weight(&self, quest: &Quest) -> f32 {
+        let category = self.category_weights.get(&quest.category).unwrap_or(&1.0);
+        let difficulty = self
+            .difficulty_weights
+            .get(&quest.difficulty)
+            .unwrap_or(&1.0);
+        (category * difficulty).max(0.0)
+    }
+
+    fn remember(&mut self, quest: &Quest) {
+        self.history.push_back(quest.id.clone());
+        while self.history.len() > self.history_len {
+            self.history.pop_front();
+        }
+    }
+}
+
+impl SelectionStrategy for WeightedRandom {
+    fn select(&mut self, quests: &[&Quest]) -> Option<usize> {
+        let fresh: Vec<usize> = (0..quests.len())
+            .filter(|&index| !self.history.contains(&quests[index].id))
+            .collect();
+        // When everything waiting was picked recently, a repeat beats no quest at all.
+        let candidates = if fresh.is_empty() {
+            (0..quests.len()).collect()
+        } else {
+            fresh
+        };
+        let weights: Vec<f32> = candidates
+            .iter()
+            .map(|&index| self.weight(quests[index]))
+            .collect();
+        let total: f32 = weights.iter().sum();
+        if total <= 0.0 {
+            return None;
+        }
+
+        let mut roll = self.rng.gen_range(0.0..total);
+        // Rounding can leave the roll past the last weight.
+        let mut picked = candidates
+            .iter()
+            .zip(&weights)
+            .rev()
+            .find(|(_, &weight)| weight > 0.0)
+            .map(|(&index, _)| index)?;
+        for (&index, &weight) in candidates.iter().zip(&weights) {
+            if weight > 0.0 && roll < weight {
+                picked = index;
+                break;
+            }
+            roll -= weight;
+        }
+        self.remember(quests[picked]);
+        Some(picked)
+    }
+}
+
+/// The strategy `check_next_quest` uses. Defaults to `Fifo`.
+#[derive(Resource)]
+pub struct QuestSelector(pub Box<dyn SelectionStrategy>);
+
+impl Default for QuestSelector {
+    fn default() -> Self {
+        QuestSelector(Box::new(Fifo))
+    }
+}
+
+impl QuestSelector {
+    /// Takes the next quest out of the pool, leaving locked and rejected quests
+    /// waiting, as well as copies of quests that are already active.
+    pub fn next(
+        &mut self,
+        quest_pool: &mut QuestPool,
+        current_quest_info: &CurrentQuestInfo,
+    ) -> Option<Quest> {
+        let unlocked: Vec<usize> = (0..quest_pool.queue.len())
+            .filter(|&index| is_candidate(quest_pool, current_quest_info, &quest_pool.queue[index]))
+            .collect();
+        let candidates: Vec<&Quest> = unlocked
+            .iter()
+            .map(|&index| &quest_pool.queue[index])
+            .collect();
+        let picked = self.0.select(&candidates)?;
+        quest_pool.queue.remove(unlocked[picked])
+    }
+}
+
+fn is_candidate(
+    quest_pool: &QuestPool,
+    current_quest_info: &CurrentQuestInfo,
+    quest: &Quest,
+) -> bool {
+    quest_pool.is_available(quest) && current_quest_info.get(&quest.id).is_none()
+}
+
+/// The quest pool together with the strategy that draws from it.
+#[derive(SystemParam)]
+pub(crate) struct QuestDraw<'w, 's> {
+    quest_pool: ResMut<'w, QuestPool>,
+    selector: ResMut<'w, QuestSelector>,
+    #[system_param(ignore)]
+    _marker: PhantomData<&'s ()>,
+}
+
+impl QuestDraw<'_, '_> {
+    pub(crate) fn next(&mut self, current_quest_info: &CurrentQuestInfo) -> Option<Quest> {
+        // Only borrowed mutably once there is something to draw, so an idle pool
+        // isn't marked as changed and saved again every frame.
+        let quest_pool = &*self.quest_pool;
+        if !quest_pool
+            .queue
+            .iter()
+            .any(|quest| is_candidate(quest_pool, current_quest_info, quest))
+        {
+            return None;
+        }
+        self.selector.next(&mut self.quest_pool, current_quest_info)
+    }
+}
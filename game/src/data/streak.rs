@@ -0,0 +1,79 @@
+use super::QuestMissedEvent;
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// Quests completed in a row, unbroken by misses.
+#[derive(Resource, Clone, Default, Serialize, Deserialize)]
+pub struct Streak {
+    pub current: u32,
+    pub best: u32,
+    /// Streak freezes in stock. Each one takes the hit of a single miss.
+    pub freezes: u32,
+}
+
+/// How a streak pays off.
+#[derive(Resource, Clone)]
+pub struct StreakRules {
+    /// Share of a completion's vitality added for every earlier completion in the streak.
+    pub bonus_per_completion: f32,
+    pub max_bonus: f32,
+    /// Every this many completions in a row, the tree grows an extra point.
+    pub growth_bonus_every: u32,
+    /// Every this many completions in a row, a streak freeze is earned.
+    pub freeze_every: u32,
+    pub max_freezes: u32,
+}
+
+impl Default for StreakRules {
+    fn default() -> Self {
+        StreakRules {
+            bonus_per_completion: 0.05,
+            max_bonus: 0.5,
+            growth_bonus_every: 5,
+            freeze_every: 7,
+            max_freezes: 3,
+        }
+    }
+}
+
+impl Streak {
+    /// Extends the streak, earning a freeze when one is due.
+    pub fn complete(&mut self, rules: &StreakRules) {
+        self.current += 1;
+        self.best = self.best.max(self.current);
+        if self.current.is_multiple_of(rules.freeze_every) {
+            self.freezes = (self.freezes + 1).min(rules.max_freezes);
+        }
+    }
+
+    /// Ends the streak, unless a freeze is used up instead. Returns whether one was.
+    pub fn miss(&mut self) -> bool {
+        if self.freezes > 0 {
+            self.freezes -= 1;
+            true
+        } else {
+            self.current = 0;
+            false
+        }
+    }
+
+    /// Share of vitality added on top of the latest completion.
+    pub fn vitality_bonus(&self, rules: &StreakRules) -> f32 {
+        (self.current.saturating_sub(1) as f32 * rules.bonus_per_completion).min(rules.max_bonus)
+    }
+
+    /// Growth points added on top of the latest completion.
+    pub fn growth_bonus(&self, rules: &StreakRules) -> u32 {
+        u32::from(self.current > 0 && self.current.is_multiple_of(rules.growth_bonus_every))
+    }
+}
+
+/// Ends the streak on a miss. Completions extend it as they are applied to the tree.
+pub(crate) fn track_streak(
+    mut quest_missed_events: EventReader<QuestMissedEvent>,
+    mut streak: ResMut<Streak>,
+) {
+    for _ in quest_missed_events.iter() {
+        streak.miss();
+    }
+}
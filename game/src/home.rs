@@ -1,15 +1,101 @@
 use bevy::prelude::*;
+use chrono::Duration;
 
+mod achievement_popup;
+mod away_summary;
+mod forest;
+mod level_up;
 mod proc_tree;
 mod quest_panel;
+mod replant;
+mod shop;
+mod stats;
 
+use achievement_popup::AchievementPopupPlugin;
+use away_summary::AwaySummaryPlugin;
+use forest::ForestPlugin;
+use level_up::LevelUpPlugin;
 use proc_tree::ProcTreePlugin;
+pub use proc_tree::{generate_tree_image, TreeKindTable};
 use quest_panel::QuestPanelPlugin;
+use replant::ReplantPlugin;
+use shop::ShopPlugin;
+use stats::StatsPlugin;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum HomeScreen {
+    Home,
+    AwaySummary,
+    Replant,
+    Stats,
+    Shop,
+}
 
 pub struct HomePlugin;
 
 impl Plugin for HomePlugin {
     fn build(&self, app: &mut App) {
-        app.add_plugin(QuestPanelPlugin).add_plugin(ProcTreePlugin);
+        app.add_state(HomeScreen::Home)
+            .add_plugin(QuestPanelPlugin)
+            .add_plugin(ProcTreePlugin)
+            .add_plugin(ForestPlugin)
+            .add_plugin(AwaySummaryPlugin)
+            .add_plugin(ReplantPlugin)
+            .add_plugin(StatsPlugin)
+            .add_plugin(ShopPlugin)
+            .add_plugin(AchievementPopupPlugin)
+            .add_plugin(LevelUpPlugin);
+
+        #[cfg(debug_assertions)]
+        app.add_system(fast_forward_clock);
+    }
+}
+
+/// Moves to `to`, unless another screen change is already queued this frame. The first
+/// change wins then, instead of panicking; screens opened by their conditions, like a
+/// dead tree, are opened again on a later frame.
+fn switch_screen(screen: &mut State<HomeScreen>, to: HomeScreen) {
+    if let Err(err) = screen.set(to) {
+        debug!("Not switching to {:?}: {:?}", to, err);
+    }
+}
+
+fn format_duration(duration: Duration) -> String {
+    if duration >= Duration::days(365) {
+        let years = duration.num_days() / 365;
+        years.to_string() + " years"
+    } else if duration >= Duration::weeks(1) {
+        duration.num_weeks().to_string() + "w"
+    } else if duration >= Duration::days(1) {
+        duration.num_days().to_string() + "d"
+    } else if duration >= Duration::hours(1) {
+        duration.num_hours().to_string() + "h"
+    } else {
+        duration.num_seconds().max(0).to_string() + "s"
+    }
+}
+
+fn cursor_to_world(window: &Window, cam_transform: &Transform, cursor_pos: Vec2) -> Vec2 {
+    // get the size of the window
+    let size = Vec2::new(window.width(), window.height());
+
+    // the default orthographic projection is in pixels from the center;
+    // just undo the translation
+    let screen_pos = cursor_pos - size / 2.0;
+
+    // apply the camera transform
+    let out = cam_transform.compute_matrix() * screen_pos.extend(0.0).extend(1.0);
+    Vec2::new(out.x, out.y)
+}
+
+/// Playtesting shortcut: `F` skips a minute ahead, `Shift+F` skips an hour.
+#[cfg(debug_assertions)]
+fn fast_forward_clock(keys: Res<Input<KeyCode>>, mut clock: ResMut<crate::clock::GameClock>) {
+    if keys.just_pressed(KeyCode::F) {
+        if keys.pressed(KeyCode::LShift) || keys.pressed(KeyCode::RShift) {
+            clock.advance(Duration::hours(1));
+        } else {
+            clock.advance(Duration::minutes(1));
+        }
     }
 }
@@ -0,0 +1,96 @@
+use crate::data::{achievement, AchievementUnlockedEvent};
+use bevy::prelude::*;
+use bevy_easings::{Ease, EaseFunction, EasingType};
+
+/// How long an unlock banner stays up, including sliding in.
+const BANNER_SECS: f32 = 3.0;
+
+pub struct AchievementPopupPlugin;
+
+impl Plugin for AchievementPopupPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_system(spawn_achievement_banners)
+            .add_system(despawn_achievement_banners);
+    }
+}
+
+#[derive(Component)]
+struct AchievementBanner(Timer);
+
+/// Slides a banner naming each unlocked achievement down from the top of the screen.
+fn spawn_achievement_banners(
+    mut commands: Commands,
+    mut achievement_unlocked_events: EventReader<AchievementUnlockedEvent>,
+    asset_server: Res<AssetServer>,
+) {
+    let font = asset_server.load("fonts/at01.ttf");
+    for (index, event) in achievement_unlocked_events.iter().enumerate() {
+        let Some(achievement) = achievement(event.id) else {
+            continue;
+        };
+        // Banners unlocked together stack below each other.
+        let top = 16.0 + 96.0 * index as f32;
+        let style = |top: f32| Style {
+            position_type: PositionType::Absolute,
+            position: UiRect {
+                top: Val::Px(top),
+                left: Val::Percent(10.0),
+                right: Val::Percent(10.0),
+                ..default()
+            },
+            flex_direction: FlexDirection::Column,
+            align_items: AlignItems::Center,
+            padding: UiRect::all(Val::Px(8.0)),
+            ..default()
+        };
+
+        commands
+            .spawn(NodeBundle {
+                style: style(-96.0),
+                background_color: Color::rgb(0.95, 0.85, 0.45).into(),
+                z_index: ZIndex::Global(20),
+                ..default()
+            })
+            .insert(style(-96.0).ease_to(
+                style(top),
+                EaseFunction::QuadraticOut,
+                EasingType::Once {
+                    duration: std::time::Duration::from_millis(500),
+                },
+            ))
+            .insert(AchievementBanner(Timer::from_seconds(
+                BANNER_SECS,
+                TimerMode::Once,
+            )))
+            .with_children(|parent| {
+                parent.spawn(TextBundle::from_section(
+                    format!("Achievement: {}", achievement.name),
+                    TextStyle {
+                        font: font.clone(),
+                        font_size: 40.0,
+                        color: Color::BLACK,
+                    },
+                ));
+                parent.spawn(TextBundle::from_section(
+                    achievement.description,
+                    TextStyle {
+                        font: font.clone(),
+                        font_size: 28.0,
+                        color: Color::DARK_GRAY,
+                    },
+                ));
+            });
+    }
+}
+
+fn despawn_achievement_banners(
+    mut commands: Commands,
+    mut banners: Query<(Entity, &mut AchievementBanner)>,
+    time: Res<Time>,
+) {
+    for (entity, mut banner) in banners.iter_mut() {
+        if banner.0.tick(time.delta()).finished() {
+            commands.entity(entity).despawn_recursive();
+        }
+    }
+}
@@ -0,0 +1,125 @@
+use super::{format_duration, switch_screen, HomeScreen};
+use crate::data::AwaySummary;
+use bevy::prelude::*;
+
+pub struct AwaySummaryPlugin;
+
+impl Plugin for AwaySummaryPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_system_set(SystemSet::on_update(HomeScreen::Home).with_system(open_away_summary))
+            .add_system_set(
+                SystemSet::on_enter(HomeScreen::AwaySummary).with_system(setup_away_summary),
+            )
+            .add_system_set(
+                SystemSet::on_update(HomeScreen::AwaySummary).with_system(dismiss_away_summary),
+            )
+            .add_system_set(
+                SystemSet::on_exit(HomeScreen::AwaySummary).with_system(cleanup_away_summary),
+            );
+    }
+}
+
+#[derive(Component)]
+struct AwaySummaryRoot;
+
+#[derive(Component)]
+struct AwaySummaryButton;
+
+fn open_away_summary(summary: Option<Res<AwaySummary>>, mut screen: ResMut<State<HomeScreen>>) {
+    // Until dismissed, which removes the summary.
+    if summary.is_some() {
+        switch_screen(&mut screen, HomeScreen::AwaySummary);
+    }
+}
+
+fn setup_away_summary(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    summary: Res<AwaySummary>,
+) {
+    let font = asset_server.load("fonts/at01.ttf");
+    let header_text_style = TextStyle {
+        font: font.clone(),
+        font_size: 48.0,
+        color: Color::BLACK,
+    };
+    let text_style = TextStyle {
+        font,
+        font_size: 32.0,
+        color: Color::DARK_GRAY,
+    };
+
+    let mut lines = vec![format!(
+        "You were away for {}",
+        format_duration(summary.away_for)
+    )];
+    lines.extend(
+        summary
+            .missed_quests
+            .iter()
+            .map(|quest_name| format!("Missed: {}", quest_name)),
+    );
+    lines.push(format!(
+        "Health: {:?} -> {:?}",
+        summary.health_before, summary.health_after
+    ));
+
+    commands
+        .spawn(NodeBundle {
+            style: Style {
+                size: Size::new(Val::Percent(100.0), Val::Percent(100.0)),
+                position_type: PositionType::Absolute,
+                flex_direction: FlexDirection::Column,
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                ..default()
+            },
+            background_color: Color::rgba(1.0, 1.0, 1.0, 0.85).into(),
+            z_index: ZIndex::Global(10),
+            ..default()
+        })
+        .insert(AwaySummaryRoot)
+        .with_children(|parent| {
+            parent.spawn(TextBundle::from_section(
+                "While you were away",
+                header_text_style.clone(),
+            ));
+            for line in lines {
+                parent.spawn(TextBundle::from_section(line, text_style.clone()));
+            }
+            parent
+                .spawn(ButtonBundle {
+                    style: Style {
+                        margin: UiRect::top(Val::Px(24.0)),
+                        padding: UiRect::all(Val::Px(8.0)),
+                        ..default()
+                    },
+                    background_color: Color::rgb(0.6, 0.8, 0.5).into(),
+                    ..default()
+                })
+                .insert(AwaySummaryButton)
+                .with_children(|parent| {
+                    parent.spawn(TextBundle::from_section("Continue", header_text_style));
+                });
+        });
+}
+
+fn dismiss_away_summary(
+    mut commands: Commands,
+    buttons: Query<&Interaction, (Changed<Interaction>, With<AwaySummaryButton>)>,
+    mut screen: ResMut<State<HomeScreen>>,
+) {
+    if buttons
+        .iter()
+        .any(|interaction| *interaction == Interaction::Clicked)
+    {
+        commands.remove_resource::<AwaySummary>();
+        switch_screen(&mut screen, HomeScreen::Home);
+    }
+}
+
+fn cleanup_away_summary(mut commands: Commands, roots: Query<Entity, With<AwaySummaryRoot>>) {
+    for root in roots.iter() {
+        commands.entity(root).despawn_recursive();
+    }
+}
@@ -0,0 +1,71 @@
+use super::HomeScreen;
+use crate::data::{can_plant, PlantTreeEvent, TreeInfo};
+use bevy::prelude::*;
+
+pub struct ForestPlugin;
+
+impl Plugin for ForestPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_startup_system(setup_plant_button)
+            .add_system(update_plant_button)
+            .add_system_set(SystemSet::on_update(HomeScreen::Home).with_system(press_plant_button));
+    }
+}
+
+#[derive(Component)]
+struct PlantButton;
+
+fn setup_plant_button(mut commands: Commands, asset_server: Res<AssetServer>) {
+    let text_style = TextStyle {
+        font: asset_server.load("fonts/at01.ttf"),
+        font_size: 32.0,
+        color: Color::BLACK,
+    };
+
+    commands
+        .spawn(ButtonBundle {
+            style: Style {
+                position_type: PositionType::Absolute,
+                position: UiRect {
+                    top: Val::Px(16.0),
+                    right: Val::Px(16.0),
+                    ..default()
+                },
+                padding: UiRect::all(Val::Px(8.0)),
+                display: Display::None,
+                ..default()
+            },
+            background_color: Color::rgb(0.6, 0.8, 0.5).into(),
+            ..default()
+        })
+        .insert(PlantButton)
+        .with_children(|parent| {
+            parent.spawn(TextBundle::from_section("Plant a tree", text_style));
+        });
+}
+
+/// The button only shows up once the forest has reached its next planting milestone.
+fn update_plant_button(mut buttons: Query<&mut Style, With<PlantButton>>, trees: Query<&TreeInfo>) {
+    let display = if can_plant(trees.iter()) {
+        Display::Flex
+    } else {
+        Display::None
+    };
+    for mut style in buttons.iter_mut() {
+        if style.display != display {
+            style.display = display;
+        }
+    }
+}
+
+fn press_plant_button(
+    buttons: Query<&Interaction, (Changed<Interaction>, With<PlantButton>)>,
+    mut plant_tree_events: EventWriter<PlantTreeEvent>,
+) {
+    if buttons
+        .iter()
+        .any(|interaction| *interaction == Interaction::Clicked)
+    {
+        plant_tree_events.send(PlantTreeEvent);
+    }
+}
@@ -0,0 +1,165 @@
+use super::proc_tree::ProcTree;
+use crate::data::{unlocks_at, CurrentTree, LevelUpEvent, Unlock};
+use bevy::math::{vec2, vec3};
+use bevy::prelude::*;
+use bevy_easings::{Ease, EaseFunction, EasingType};
+use std::f32::consts::TAU;
+
+/// How long the celebration stays up, including the burst of leaves.
+const CELEBRATION_SECS: f32 = 3.0;
+
+/// Leaves bursting out of the selected tree.
+const BURST_LEAVES: usize = 16;
+
+/// How far the leaves fly, in tree texture pixels.
+const BURST_RADIUS: f32 = 28.0;
+
+pub struct LevelUpPlugin;
+
+impl Plugin for LevelUpPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_system(celebrate_level_up)
+            .add_system(end_celebrations);
+    }
+}
+
+#[derive(Component)]
+struct Celebration(Timer);
+
+fn unlock_line(unlock: Unlock) -> String {
+    match unlock {
+        Unlock::TreeKind(kind) => format!("New tree: {:?}", kind),
+        Unlock::QuestCategory(category) => format!("New quests: {}", category),
+    }
+}
+
+/// Pops a banner naming the new level and what it unlocks, while leaves burst out of the
+/// selected tree.
+fn celebrate_level_up(
+    mut commands: Commands,
+    mut level_up_events: EventReader<LevelUpEvent>,
+    asset_server: Res<AssetServer>,
+    current_tree: Res<CurrentTree>,
+    proc_trees: Query<(Entity, &ProcTree)>,
+) {
+    // Only the highest level is celebrated when several are reached at once.
+    let Some(level) = level_up_events.iter().map(|event| event.level).max() else {
+        return;
+    };
+    let font = asset_server.load("fonts/at01.ttf");
+    let lines: Vec<String> = unlocks_at(level).map(unlock_line).collect();
+
+    let style = |scale: f32| Style {
+        position_type: PositionType::Absolute,
+        position: UiRect {
+            top: Val::Percent(30.0),
+            left: Val::Percent(50.0 - 20.0 * scale),
+            right: Val::Percent(50.0 - 20.0 * scale),
+            ..default()
+        },
+        flex_direction: FlexDirection::Column,
+        align_items: AlignItems::Center,
+        padding: UiRect::all(Val::Px(12.0)),
+        ..default()
+    };
+    commands
+        .spawn(NodeBundle {
+            style: style(0.0),
+            background_color: Color::rgb(0.95, 0.85, 0.45).into(),
+            z_index: ZIndex::Global(20),
+            ..default()
+        })
+        .insert(style(0.0).ease_to(
+            style(1.0),
+            EaseFunction::BackOut,
+            EasingType::Once {
+                duration: std::time::Duration::from_millis(400),
+            },
+        ))
+        .insert(Celebration(Timer::from_seconds(
+            CELEBRATION_SECS,
+            TimerMode::Once,
+        )))
+        .with_children(|parent| {
+            parent.spawn(TextBundle::from_section(
+                format!("Level {}!", level),
+                TextStyle {
+                    font: font.clone(),
+                    font_size: 56.0,
+                    color: Color::BLACK,
+                },
+            ));
+            for line in lines {
+                parent.spawn(TextBundle::from_section(
+                    line,
+                    TextStyle {
+                        font: font.clone(),
+                        font_size: 32.0,
+                        color: Color::DARK_GRAY,
+                    },
+                ));
+            }
+        });
+
+    let Some((tree_sprite, _)) = proc_trees
+        .iter()
+        .find(|(_, proc_tree)| proc_tree.tree == current_tree.0)
+    else {
+        return;
+    };
+    commands.entity(tree_sprite).with_children(|parent| {
+        for index in 0..BURST_LEAVES {
+            let angle = TAU * index as f32 / BURST_LEAVES as f32;
+            let (r, g, b) = if index.is_multiple_of(2) {
+                (0.95, 0.85, 0.45)
+            } else {
+                (0.45, 0.68, 0.22)
+            };
+            let start = vec3(0.0, 8.0, 0.01);
+            let end = start + (vec2(angle.cos(), angle.sin()) * BURST_RADIUS).extend(0.0);
+            parent
+                .spawn(SpriteBundle::default())
+                .insert(Transform::from_translation(start).ease_to(
+                    Transform::from_translation(end),
+                    EaseFunction::QuadraticOut,
+                    EasingType::Once {
+                        duration: std::time::Duration::from_millis(1200),
+                    },
+                ))
+                .insert(
+                    Sprite {
+                        color: Color::rgb(r, g, b),
+                        custom_size: Some(vec2(3.0, 3.0)),
+                        ..default()
+                    }
+                    .ease_to(
+                        Sprite {
+                            color: Color::rgba(r, g, b, 0.0),
+                            custom_size: Some(vec2(3.0, 3.0)),
+                            ..default()
+                        },
+                        EaseFunction::QuadraticIn,
+                        EasingType::Once {
+                            duration: std::time::Duration::from_millis(1200),
+                        },
+                    ),
+                )
+                .insert(Celebration(Timer::from_seconds(
+                    CELEBRATION_SECS,
+                    TimerMode::Once,
+                )));
+        }
+    });
+}
+
+fn end_celebrations(
+    mut commands: Commands,
+    mut celebrations: Query<(Entity, &mut Celebration)>,
+    time: Res<Time>,
+) {
+    for (entity, mut celebration) in celebrations.iter_mut() {
+        if celebration.0.tick(time.delta()).finished() {
+            commands.entity(entity).despawn_recursive();
+        }
+    }
+}
@@ -1,104 +1,407 @@
-use crate::data::{CurrentTree, Health, QuestCompletedEvent, QuestMissedEvent, TreeInfo};
+use super::quest_panel::QuestPanel;
+use super::{cursor_to_world, HomeScreen};
+use crate::clock::GameClock;
+use crate::data::{
+    CurrentTree, Health, QuestCompletedEvent, QuestMissedEvent, TreeInfo, TreeKind, Wardrobe,
+};
 use bevy::app::{App, Plugin};
-use bevy::math::{vec2, vec3};
+use bevy::math::{vec2, vec3, vec4};
 use bevy::prelude::*;
+use bevy::sprite::{Material2dPlugin, MaterialMesh2dBundle, Mesh2dHandle};
 use bevy_easings::{Ease, EaseFunction, EasingType};
+use chrono::Datelike;
+
+mod cosmetics;
+mod generator;
+mod kind_table;
+mod material;
+
+use cosmetics::{dress_proc_trees, skin_tint};
+pub use generator::generate_tree_image;
+use generator::CANVAS_SIZE;
+pub use kind_table::TreeKindTable;
+use kind_table::{Leaves, TreeKindTableLoader};
+use material::{desaturation, season_tint, ProcTreeMaterial, WIND_STRENGTH};
+
+/// How long the tree takes to stretch into its next growth stage.
+const STAGE_GROWTH_MILLIS: u64 = 1200;
+
+/// Width of the forest grid; a lone tree spans all of it.
+const FOREST_WIDTH: f32 = 360.0;
+
+/// Where the base of the front row of trees stands.
+const FOREST_GROUND: f32 = -(CANVAS_SIZE as f32 * 6.0) / 2.0;
+
+/// A dead tree has lost all of its leaves.
+const WITHERED_LEAVES: Leaves = Leaves {
+    density: 0.0,
+    color: (0.0, 0.0, 0.0),
+};
+
+/// Tint of the trees that aren't selected, so the selected one stands out.
+const UNSELECTED_TINT: f32 = 0.75;
 
 pub struct ProcTreePlugin;
 
 impl Plugin for ProcTreePlugin {
     fn build(&self, app: &mut App) {
         app.insert_resource(ProcTreeSystem::default())
-            .add_startup_system(setup_proc_tree)
+            .add_asset::<TreeKindTable>()
+            .init_asset_loader::<TreeKindTableLoader>()
+            .add_plugin(Material2dPlugin::<ProcTreeMaterial>::default())
             .add_startup_system(setup_proc_tree_plugin)
-            .add_system(update_proc_tree)
+            .add_system(spawn_proc_trees)
+            .add_system(despawn_proc_trees)
+            .add_system(reset_proc_trees_on_reload)
+            .add_system(update_proc_tree.after(reset_proc_trees_on_reload))
+            .add_system(place_proc_tree.after(reset_proc_trees_on_reload))
+            .add_system(animate_proc_tree_material)
+            .add_system(dress_proc_trees)
+            .add_system_set(
+                SystemSet::on_update(HomeScreen::Home).with_system(select_tree_on_click),
+            )
             .add_system(handle_quest_events);
     }
 }
 
+/// Everything the generated texture of a tree depends on.
+#[derive(Clone, PartialEq)]
+struct TreeLook {
+    seed: u64,
+    kind: TreeKind,
+    stage: usize,
+    health: Health,
+    dead: bool,
+}
+
+/// Everything the size and position of a tree sprite depend on.
+#[derive(Clone, Copy, PartialEq)]
+struct Placement {
+    stage: usize,
+    plot: usize,
+    tree_count: usize,
+}
+
+/// The rendered sprite of a `TreeItem`.
 #[derive(Component)]
-struct ProcTree;
+pub struct ProcTree {
+    pub tree: Entity,
+    /// What the current texture was generated for, so it is only regenerated on change.
+    look: Option<TreeLook>,
+    /// What the sprite is placed for, so crossing into the next stage can be animated.
+    placement: Option<Placement>,
+}
 
 #[derive(Default, Resource)]
 struct ProcTreeSystem {
     pub good_popup_handle: Handle<Image>,
     pub bad_popup_handle: Handle<Image>,
+    pub kind_table_handle: Handle<TreeKindTable>,
+    pub mesh_handle: Mesh2dHandle,
 }
 
 fn setup_proc_tree_plugin(
     asset_server: Res<AssetServer>,
+    mut meshes: ResMut<Assets<Mesh>>,
     mut proc_tree_system: ResMut<ProcTreeSystem>,
 ) {
     proc_tree_system.good_popup_handle = asset_server.load("sprites/heart.png");
     proc_tree_system.bad_popup_handle = asset_server.load("sprites/emote_broken_heart.png");
+    proc_tree_system.kind_table_handle = asset_server.load("trees/trees.kinds.ron");
+    let canvas_size = Vec2::splat(CANVAS_SIZE as f32);
+    proc_tree_system.mesh_handle = meshes.add(shape::Quad::new(canvas_size).into()).into();
 }
 
-fn setup_proc_tree(
+fn spawn_proc_trees(
     mut commands: Commands,
-    asset_server: Res<AssetServer>,
-    mut texture_atlases: ResMut<Assets<TextureAtlas>>,
+    planted_trees: Query<Entity, Added<TreeInfo>>,
+    proc_tree_system: Res<ProcTreeSystem>,
+    mut materials: ResMut<Assets<ProcTreeMaterial>>,
 ) {
-    let texture_handle = asset_server.load("sprites/season-trees-spritesheet.png");
-    let texture_atlas =
-        TextureAtlas::from_grid(texture_handle, Vec2::new(64.0, 64.0), 40, 1, None, None);
-    let texture_atlas_handle = texture_atlases.add(texture_atlas);
-    commands
-        .spawn(SpriteSheetBundle {
-            texture_atlas: texture_atlas_handle,
-            transform: Transform::from_scale(Vec3::splat(6.0)),
-            ..default()
-        })
-        .insert(ProcTree);
+    for tree in planted_trees.iter() {
+        commands
+            .spawn(MaterialMesh2dBundle {
+                mesh: proc_tree_system.mesh_handle.clone(),
+                material: materials.add(ProcTreeMaterial::default()),
+                visibility: Visibility::INVISIBLE,
+                ..default()
+            })
+            .insert(ProcTree {
+                tree,
+                look: None,
+                placement: None,
+            });
+    }
+}
+
+/// Clears away the sprites of trees that were removed from the forest.
+fn despawn_proc_trees(
+    mut commands: Commands,
+    proc_trees: Query<(Entity, &ProcTree)>,
+    trees_info: Query<&TreeInfo>,
+) {
+    for (entity, proc_tree) in proc_trees.iter() {
+        if trees_info.get(proc_tree.tree).is_err() {
+            commands.entity(entity).despawn_recursive();
+        }
+    }
+}
+
+fn reset_proc_trees_on_reload(
+    mut asset_events: EventReader<AssetEvent<TreeKindTable>>,
+    mut proc_trees: Query<&mut ProcTree>,
+) {
+    let reloaded = asset_events
+        .iter()
+        .any(|event| matches!(event, AssetEvent::Modified { .. }));
+    if reloaded {
+        for mut proc_tree in proc_trees.iter_mut() {
+            proc_tree.look = None;
+            proc_tree.placement = None;
+        }
+    }
 }
 
 fn update_proc_tree(
-    mut proc_trees: Query<&mut TextureAtlasSprite, With<ProcTree>>,
+    mut proc_trees: Query<(&mut ProcTree, &Handle<ProcTreeMaterial>, &mut Visibility)>,
+    trees_info: Query<&TreeInfo>,
+    proc_tree_system: Res<ProcTreeSystem>,
+    kind_tables: Res<Assets<TreeKindTable>>,
+    mut images: ResMut<Assets<Image>>,
+    mut materials: ResMut<Assets<ProcTreeMaterial>>,
+) {
+    let Some(kind_table) = kind_tables.get(&proc_tree_system.kind_table_handle) else {
+        return;
+    };
+
+    for (mut proc_tree, material_handle, mut visibility) in proc_trees.iter_mut() {
+        let Ok(tree) = trees_info.get(proc_tree.tree) else {
+            continue;
+        };
+        let look = TreeLook {
+            seed: tree.seed,
+            kind: tree.kind,
+            stage: tree.growth.stage,
+            health: tree.health(),
+            dead: tree.is_dead(),
+        };
+        if proc_tree.look.as_ref() == Some(&look) {
+            continue;
+        }
+        let Some(params) = kind_table.kinds.get(&look.kind) else {
+            continue;
+        };
+        let Some(stage) = params.stage(look.stage) else {
+            continue;
+        };
+        let Some(material) = materials.get_mut(material_handle) else {
+            continue;
+        };
+        material.texture = images.add(generate_tree_image(
+            params,
+            look.seed,
+            look.kind,
+            stage.iterations,
+            if look.dead {
+                WITHERED_LEAVES
+            } else {
+                params.leaves.for_health(&look.health)
+            },
+        ));
+        proc_tree.look = Some(look);
+        visibility.is_visible = true;
+    }
+}
+
+/// Base position and scale factor of a plot. The grid gets denser as the forest
+/// grows, with the back rows drawn higher up and behind the front ones.
+fn plot_layout(plot: usize, tree_count: usize) -> (Vec3, f32) {
+    let columns = (tree_count as f32).sqrt().ceil().max(1.0) as usize;
+    let factor = 1.0 / columns as f32;
+    let cell_width = FOREST_WIDTH * factor;
+    let (row, column) = (plot / columns, plot % columns);
+    let base = vec3(
+        -FOREST_WIDTH / 2.0 + cell_width * (column as f32 + 0.5),
+        FOREST_GROUND + row as f32 * cell_width * 0.6,
+        -(row as f32) * 0.01,
+    );
+    (base, factor)
+}
+
+fn place_proc_tree(
+    mut commands: Commands,
+    mut proc_trees: Query<(Entity, &mut ProcTree, &mut Transform)>,
+    trees_info: Query<&TreeInfo>,
+    proc_tree_system: Res<ProcTreeSystem>,
+    kind_tables: Res<Assets<TreeKindTable>>,
+) {
+    let Some(kind_table) = kind_tables.get(&proc_tree_system.kind_table_handle) else {
+        return;
+    };
+    let tree_count = trees_info.iter().len();
+
+    for (entity, mut proc_tree, mut transform) in proc_trees.iter_mut() {
+        let Ok(tree) = trees_info.get(proc_tree.tree) else {
+            continue;
+        };
+        let placement = Placement {
+            stage: tree.growth.stage,
+            plot: tree.plot,
+            tree_count,
+        };
+        if proc_tree.placement == Some(placement) {
+            continue;
+        }
+        let Some(params) = kind_table.kinds.get(&tree.kind) else {
+            continue;
+        };
+        let Some(stage) = params.stage(placement.stage) else {
+            continue;
+        };
+
+        // Keep the base of the tree on its plot while it is scaled.
+        let (base, factor) = plot_layout(placement.plot, placement.tree_count);
+        let scale = params.scale * stage.scale * factor;
+        let target = Transform {
+            translation: base + vec3(0.0, CANVAS_SIZE as f32 * scale / 2.0, 0.0),
+            scale: Vec3::new(scale, scale, 1.0),
+            ..*transform
+        };
+
+        let grew = proc_tree
+            .placement
+            .is_some_and(|previous| previous.stage < placement.stage);
+        if grew {
+            commands.entity(entity).insert(transform.ease_to(
+                target,
+                EaseFunction::ElasticOut,
+                EasingType::Once {
+                    duration: std::time::Duration::from_millis(STAGE_GROWTH_MILLIS),
+                },
+            ));
+        } else {
+            *transform = target;
+        }
+        proc_tree.placement = Some(placement);
+    }
+}
+
+fn animate_proc_tree_material(
+    proc_trees: Query<(&ProcTree, &Handle<ProcTreeMaterial>)>,
     current_tree: Res<CurrentTree>,
     trees_info: Query<&TreeInfo>,
+    mut materials: ResMut<Assets<ProcTreeMaterial>>,
+    wardrobe: Res<Wardrobe>,
+    clock: Res<GameClock>,
+    time: Res<Time>,
+) {
+    let season = season_tint(clock.now().month());
+    let skin = skin_tint(&wardrobe);
+    let single_tree = trees_info.iter().len() == 1;
+
+    for (proc_tree, material_handle) in proc_trees.iter() {
+        let (Ok(tree), Some(material)) = (
+            trees_info.get(proc_tree.tree),
+            materials.get_mut(material_handle),
+        ) else {
+            continue;
+        };
+        let tint = if single_tree || proc_tree.tree == current_tree.0 {
+            1.0
+        } else {
+            UNSELECTED_TINT
+        };
+        material.color = season * skin * vec4(tint, tint, tint, 1.0);
+        material.desaturation = desaturation(tree);
+        material.wind = WIND_STRENGTH;
+        material.time = time.elapsed_seconds_wrapped();
+    }
+}
+
+fn select_tree_on_click(
+    proc_trees: Query<(&ProcTree, &GlobalTransform, &Visibility)>,
+    cameras: Query<&Transform, With<Camera>>,
+    panels: Query<(&QuestPanel, &Transform), Without<Camera>>,
+    interactions: Query<&Interaction>,
+    windows: Res<Windows>,
+    mouse_buttons: Res<Input<MouseButton>>,
+    mut current_tree: ResMut<CurrentTree>,
 ) {
-    let current_tree = trees_info.get(current_tree.0).unwrap();
-    for mut tree_sprite in proc_trees.iter_mut() {
-        tree_sprite.index = match current_tree.health {
-            Health::Good => 0,
-            Health::Moderate => 24,
-            Health::Bad => 32,
+    if !mouse_buttons.just_pressed(MouseButton::Left) {
+        return;
+    }
+    // Presses on the quest panel and the buttons are theirs, so a quest completed with
+    // the panel over a tree still applies to the selected one.
+    if interactions
+        .iter()
+        .any(|interaction| *interaction != Interaction::None)
+    {
+        return;
+    }
+    let window = windows.get_primary().unwrap();
+    let Some(cursor_pos) = window.cursor_position() else {
+        return;
+    };
+    let cursor_pos = cursor_to_world(window, cameras.single(), cursor_pos);
+    if panels
+        .iter()
+        .any(|(panel, transform)| panel.contains(transform, cursor_pos))
+    {
+        return;
+    }
+
+    let clicked = proc_trees
+        .iter()
+        .filter(|(_, global_transform, visibility)| {
+            let (scale, _, translation) = global_transform.to_scale_rotation_translation();
+            let half_size = CANVAS_SIZE as f32 * scale.truncate() / 2.0;
+            visibility.is_visible
+                && (cursor_pos - translation.truncate())
+                    .abs()
+                    .cmple(half_size)
+                    .all()
+        })
+        .max_by(|(_, a, _), (_, b, _)| a.translation().z.total_cmp(&b.translation().z));
+    if let Some((proc_tree, _, _)) = clicked {
+        if current_tree.0 != proc_tree.tree {
+            current_tree.0 = proc_tree.tree;
         }
     }
 }
 
 fn handle_quest_events(
     mut commands: Commands,
-    quest_completed_events: EventReader<QuestCompletedEvent>,
-    quest_missed_events: EventReader<QuestMissedEvent>,
+    mut quest_completed_events: EventReader<QuestCompletedEvent>,
+    mut quest_missed_events: EventReader<QuestMissedEvent>,
     proc_tree_system: Res<ProcTreeSystem>,
+    proc_trees: Query<(&ProcTree, &Transform)>,
 ) {
-    let quest_completed = !quest_completed_events.is_empty();
-    let quest_missed = !quest_missed_events.is_empty();
+    // Popups rise from the tree the quest result applied to.
+    let center_of = |tree: Entity| {
+        proc_trees
+            .iter()
+            .find(|(proc_tree, _)| proc_tree.tree == tree)
+            .map_or(Vec2::ZERO, |(_, transform)| {
+                transform.translation.truncate()
+            })
+    };
 
-    if quest_completed {
-        spawn_popup(
-            &mut commands,
-            proc_tree_system.good_popup_handle.clone(),
-            vec2(-50., 0.),
-            6.0,
-        );
-        spawn_popup(
-            &mut commands,
-            proc_tree_system.good_popup_handle.clone(),
-            vec2(50., 60.),
-            6.0,
-        );
-        spawn_popup(
-            &mut commands,
-            proc_tree_system.good_popup_handle.clone(),
-            vec2(30., -40.),
-            6.0,
-        );
-    } else if quest_missed {
+    for event in quest_completed_events.iter() {
+        let center = center_of(event.tree);
+        for offset in [vec2(-50., 0.), vec2(50., 60.), vec2(30., -40.)] {
+            spawn_popup(
+                &mut commands,
+                proc_tree_system.good_popup_handle.clone(),
+                center + offset,
+                6.0,
+            );
+        }
+    }
+    for event in quest_missed_events.iter() {
         spawn_popup(
             &mut commands,
             proc_tree_system.bad_popup_handle.clone(),
-            vec2(0., 0.),
+            center_of(event.tree),
             1.0,
         );
     }
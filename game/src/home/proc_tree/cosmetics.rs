@@ -0,0 +1,84 @@
+use super::generator::CANVAS_SIZE;
+use super::ProcTree;
+use crate::data::{CosmeticSlot, TreeInfo, Wardrobe};
+use bevy::math::{vec2, vec3};
+use bevy::prelude::*;
+
+/// A worn cosmetic, drawn as a child of a tree sprite.
+#[derive(Component)]
+pub(super) struct CosmeticSprite;
+
+/// Offset from the center of the tree texture and size of a slot, in texture pixels.
+/// The depth stays well within the gap between forest rows.
+fn slot_layout(slot: CosmeticSlot) -> Option<(Vec3, Vec2)> {
+    let bottom = -(CANVAS_SIZE as f32) / 2.0;
+    match slot {
+        CosmeticSlot::Pot => Some((vec3(0.0, bottom + 4.0, 0.002), vec2(14.0, 8.0))),
+        CosmeticSlot::Ground => Some((vec3(0.0, bottom + 1.0, -0.002), vec2(48.0, 2.0))),
+        CosmeticSlot::Decoration => Some((vec3(16.0, bottom + 3.0, 0.001), vec2(4.0, 6.0))),
+        CosmeticSlot::Skin => None,
+    }
+}
+
+/// Redraws the cosmetics around every tree when the worn ones change or a tree is added.
+pub(super) fn dress_proc_trees(
+    mut commands: Commands,
+    wardrobe: Res<Wardrobe>,
+    proc_trees: Query<(Entity, &ProcTree)>,
+    added_proc_trees: Query<(), Added<ProcTree>>,
+    cosmetic_sprites: Query<Entity, With<CosmeticSprite>>,
+    trees_info: Query<&TreeInfo>,
+) {
+    if !wardrobe.is_changed() && added_proc_trees.is_empty() {
+        return;
+    }
+    for sprite in cosmetic_sprites.iter() {
+        commands.entity(sprite).despawn_recursive();
+    }
+
+    let worn: Vec<(Vec3, Vec2, Color)> = [
+        CosmeticSlot::Ground,
+        CosmeticSlot::Pot,
+        CosmeticSlot::Decoration,
+    ]
+    .into_iter()
+    .filter_map(|slot| {
+        let cosmetic = wardrobe.equipped(slot)?;
+        let (offset, size) = slot_layout(slot)?;
+        let (r, g, b) = cosmetic.color;
+        Some((offset, size, Color::rgb(r, g, b)))
+    })
+    .collect();
+
+    for (entity, proc_tree) in proc_trees.iter() {
+        // Trees on their way out are left alone.
+        if trees_info.get(proc_tree.tree).is_err() {
+            continue;
+        }
+        commands.entity(entity).with_children(|parent| {
+            for (offset, size, color) in &worn {
+                parent
+                    .spawn(SpriteBundle {
+                        sprite: Sprite {
+                            color: *color,
+                            custom_size: Some(*size),
+                            ..default()
+                        },
+                        transform: Transform::from_translation(*offset),
+                        ..default()
+                    })
+                    .insert(CosmeticSprite);
+            }
+        });
+    }
+}
+
+/// Tint of the worn skin, multiplied over the tree.
+pub(super) fn skin_tint(wardrobe: &Wardrobe) -> Vec4 {
+    wardrobe
+        .equipped(CosmeticSlot::Skin)
+        .map_or(Vec4::ONE, |cosmetic| {
+            let (r, g, b) = cosmetic.color;
+            Vec4::new(r, g, b, 1.0)
+        })
+}
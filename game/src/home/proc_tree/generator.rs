@@ -0,0 +1,263 @@
+//! Seeded L-system tree generator rasterized into a small pixel-art texture.
+
+use super::kind_table::{Leaves, TreeKindParams};
+use crate::data::TreeKind;
+use bevy::math::vec2;
+use bevy::prelude::*;
+use bevy::render::render_resource::{Extent3d, TextureDimension, TextureFormat};
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+
+/// Width and height of a generated tree texture, in pixels.
+pub const CANVAS_SIZE: u32 = 64;
+
+const CANVAS_MARGIN: f32 = 2.0;
+
+/// Leaf clusters are rolled from their own stream, so a change in health
+/// thins out the crown without reshuffling the branches.
+const LEAF_SEED_SALT: u64 = 0x5eed_1eaf_0000_0001;
+
+struct Segment {
+    from: Vec2,
+    to: Vec2,
+    width: f32,
+}
+
+/// Branches and leaf cluster positions in turtle space, with the root at the origin.
+struct TreeShape {
+    segments: Vec<Segment>,
+    leaf_spots: Vec<Vec2>,
+}
+
+#[derive(Clone, Copy)]
+struct Turtle {
+    position: Vec2,
+    heading: f32,
+    depth: i32,
+}
+
+fn kind_salt(kind: TreeKind) -> u64 {
+    match kind {
+        TreeKind::Birch => 0xb1c4_0000_0000_0000,
+        TreeKind::Oak => 0x0a4a_0000_0000_0000,
+        TreeKind::Pine => 0x9143_0000_0000_0000,
+        TreeKind::Cherry => 0xc4e2_0000_0000_0000,
+    }
+}
+
+fn expand(params: &TreeKindParams, rng: &mut ChaCha8Rng, iterations: u32) -> String {
+    let mut current = params.axiom.clone();
+    for _ in 0..iterations {
+        let mut next = String::with_capacity(current.len() * 3);
+        for symbol in current.chars() {
+            match params.rules.get(&symbol) {
+                Some(alternatives) if !alternatives.is_empty() => {
+                    next.push_str(&alternatives[rng.gen_range(0..alternatives.len())])
+                }
+                _ => next.push(symbol),
+            }
+        }
+        current = next;
+    }
+    current
+}
+
+fn grow(params: &TreeKindParams, seed: u64, kind: TreeKind, iterations: u32) -> TreeShape {
+    let mut rng = ChaCha8Rng::seed_from_u64(seed ^ kind_salt(kind));
+    let instructions = expand(params, &mut rng, iterations);
+
+    let mut shape = TreeShape {
+        segments: Vec::new(),
+        leaf_spots: Vec::new(),
+    };
+    let mut turtle = Turtle {
+        position: Vec2::ZERO,
+        heading: 0.0,
+        depth: 0,
+    };
+    let mut stack = Vec::new();
+    let turn = |rng: &mut ChaCha8Rng| {
+        (params.angle + rng.gen_range(-params.angle_jitter..=params.angle_jitter)).to_radians()
+    };
+
+    for symbol in instructions.chars() {
+        match symbol {
+            'F' => {
+                let direction = vec2(-turtle.heading.sin(), turtle.heading.cos());
+                let to = turtle.position + direction * params.segment_length;
+                shape.segments.push(Segment {
+                    from: turtle.position,
+                    to,
+                    width: params.trunk_width * params.width_decay.powi(turtle.depth),
+                });
+                turtle.position = to;
+            }
+            '+' => turtle.heading += turn(&mut rng),
+            '-' => turtle.heading -= turn(&mut rng),
+            '[' => {
+                stack.push(turtle);
+                turtle.depth += 1;
+            }
+            ']' => {
+                if let Some(saved) = stack.pop() {
+                    turtle = saved;
+                }
+            }
+            'X' => shape.leaf_spots.push(turtle.position),
+            _ => {}
+        }
+    }
+
+    shape
+}
+
+struct Canvas {
+    pixels: Vec<u8>,
+}
+
+impl Canvas {
+    fn new() -> Self {
+        Canvas {
+            pixels: vec![0; (CANVAS_SIZE * CANVAS_SIZE * 4) as usize],
+        }
+    }
+
+    fn put(&mut self, x: i32, y: i32, color: (f32, f32, f32)) {
+        if x < 0 || y < 0 || x >= CANVAS_SIZE as i32 || y >= CANVAS_SIZE as i32 {
+            return;
+        }
+        let offset = ((y as u32 * CANVAS_SIZE + x as u32) * 4) as usize;
+        let to_byte = |channel: f32| (channel.clamp(0.0, 1.0) * 255.0).round() as u8;
+        self.pixels[offset..offset + 4].copy_from_slice(&[
+            to_byte(color.0),
+            to_byte(color.1),
+            to_byte(color.2),
+            255,
+        ]);
+    }
+
+    fn fill_square(&mut self, center: Vec2, size: f32, color: (f32, f32, f32)) {
+        let half = size.max(1.0) / 2.0;
+        for y in (center.y - half).round() as i32..(center.y + half).round() as i32 {
+            for x in (center.x - half).round() as i32..(center.x + half).round() as i32 {
+                self.put(x, y, color);
+            }
+        }
+    }
+}
+
+/// Maps turtle space onto the canvas, shrinking the tree if it would not fit.
+struct Projection {
+    origin: Vec2,
+    scale: f32,
+}
+
+impl Projection {
+    fn fit(shape: &TreeShape, params: &TreeKindParams) -> Self {
+        let mut min = Vec2::splat(f32::MAX);
+        let mut max = Vec2::splat(f32::MIN);
+        for segment in shape.segments.iter() {
+            let extent = Vec2::splat(segment.width / 2.0);
+            min = min.min(segment.from - extent).min(segment.to - extent);
+            max = max.max(segment.from + extent).max(segment.to + extent);
+        }
+        for spot in shape.leaf_spots.iter() {
+            let extent = Vec2::splat(params.leaf_radius);
+            min = min.min(*spot - extent);
+            max = max.max(*spot + extent);
+        }
+        if min.x > max.x {
+            return Projection {
+                origin: Vec2::ZERO,
+                scale: 1.0,
+            };
+        }
+
+        let available = CANVAS_SIZE as f32 - 2.0 * CANVAS_MARGIN;
+        let size = max - min;
+        let scale = (available / size.x).min(available / size.y).min(1.0);
+        Projection {
+            origin: vec2((min.x + max.x) / 2.0, min.y),
+            scale,
+        }
+    }
+
+    fn project(&self, point: Vec2) -> Vec2 {
+        let relative = (point - self.origin) * self.scale;
+        vec2(
+            CANVAS_SIZE as f32 / 2.0 + relative.x,
+            CANVAS_SIZE as f32 - CANVAS_MARGIN - relative.y,
+        )
+    }
+}
+
+fn rasterize(shape: &TreeShape, params: &TreeKindParams, leaves: Leaves, seed: u64) -> Canvas {
+    let mut canvas = Canvas::new();
+    let projection = Projection::fit(shape, params);
+
+    for segment in shape.segments.iter() {
+        let from = projection.project(segment.from);
+        let to = projection.project(segment.to);
+        let steps = (from.distance(to) * 2.0).ceil().max(1.0) as i32;
+        for step in 0..=steps {
+            let point = from.lerp(to, step as f32 / steps as f32);
+            canvas.fill_square(point, segment.width * projection.scale, params.trunk_color);
+        }
+    }
+
+    let mut rng = ChaCha8Rng::seed_from_u64(seed ^ LEAF_SEED_SALT);
+    let radius = (params.leaf_radius * projection.scale).max(1.0);
+    for spot in shape.leaf_spots.iter() {
+        let roll: f32 = rng.gen();
+        let brightness: f32 = rng.gen_range(0.85..1.15);
+        if roll >= leaves.density {
+            continue;
+        }
+
+        let center = projection.project(*spot);
+        let reach = radius.ceil() as i32;
+        for dy in -reach..=reach {
+            for dx in -reach..=reach {
+                if (dx * dx + dy * dy) as f32 > radius * radius {
+                    continue;
+                }
+                // Shade the lower half of every cluster for a bit of pixel-art volume.
+                let shade = if dy > 0 { 0.8 } else { 1.0 } * brightness;
+                canvas.put(
+                    center.x.round() as i32 + dx,
+                    center.y.round() as i32 + dy,
+                    (
+                        leaves.color.0 * shade,
+                        leaves.color.1 * shade,
+                        leaves.color.2 * shade,
+                    ),
+                );
+            }
+        }
+    }
+
+    canvas
+}
+
+/// Generates the texture of a tree. The same seed, kind, stage and health always
+/// produce the same pixels.
+pub fn generate_tree_image(
+    params: &TreeKindParams,
+    seed: u64,
+    kind: TreeKind,
+    iterations: u32,
+    leaves: Leaves,
+) -> Image {
+    let shape = grow(params, seed, kind, iterations);
+    let canvas = rasterize(&shape, params, leaves, seed);
+    Image::new(
+        Extent3d {
+            width: CANVAS_SIZE,
+            height: CANVAS_SIZE,
+            depth_or_array_layers: 1,
+        },
+        TextureDimension::D2,
+        canvas.pixels,
+        TextureFormat::Rgba8UnormSrgb,
+    )
+}
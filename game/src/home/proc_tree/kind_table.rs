@@ -0,0 +1,99 @@
+use crate::data::{Health, TreeKind};
+use bevy::asset::{AssetLoader, LoadContext, LoadedAsset};
+use bevy::reflect::TypeUuid;
+use bevy::utils::{BoxedFuture, HashMap};
+use serde::Deserialize;
+
+/// How every tree kind is grown by the procedural generator.
+#[derive(Deserialize, TypeUuid)]
+#[uuid = "a4c1f0d2-58e3-4b7a-9c61-0e2d7f3b9a15"]
+pub struct TreeKindTable {
+    pub kinds: HashMap<TreeKind, TreeKindParams>,
+}
+
+#[derive(Deserialize)]
+pub struct TreeKindParams {
+    /// Sprite scale of a fully grown tree.
+    pub scale: f32,
+    /// L-system start string. `F` grows a branch segment, `X` ends in a leaf cluster,
+    /// `+`/`-` turn and `[`/`]` push and pop a branch.
+    pub axiom: String,
+    /// Stochastic rewrite rules: each symbol is replaced by one of its alternatives.
+    pub rules: HashMap<char, Vec<String>>,
+    /// Turn angle in degrees.
+    pub angle: f32,
+    pub angle_jitter: f32,
+    pub segment_length: f32,
+    pub trunk_width: f32,
+    /// Branch width multiplier for every nesting level.
+    pub width_decay: f32,
+    pub trunk_color: (f32, f32, f32),
+    pub leaf_radius: f32,
+    pub leaves: HealthLeaves,
+    /// Growth stages, from sapling to fully grown.
+    pub stages: Vec<StageParams>,
+}
+
+#[derive(Deserialize)]
+pub struct HealthLeaves {
+    pub good: Leaves,
+    pub moderate: Leaves,
+    pub bad: Leaves,
+}
+
+#[derive(Deserialize, Clone, Copy)]
+pub struct Leaves {
+    /// Chance for a leaf cluster to be drawn, from 0 to 1.
+    pub density: f32,
+    pub color: (f32, f32, f32),
+}
+
+#[derive(Deserialize)]
+pub struct StageParams {
+    pub iterations: u32,
+    #[serde(default = "full_scale")]
+    pub scale: f32,
+}
+
+fn full_scale() -> f32 {
+    1.0
+}
+
+impl TreeKindParams {
+    /// The stage is clamped, so trees past the last listed stage keep its look.
+    pub fn stage(&self, stage: usize) -> Option<&StageParams> {
+        self.stages
+            .get(stage.min(self.stages.len().checked_sub(1)?))
+    }
+}
+
+impl HealthLeaves {
+    pub fn for_health(&self, health: &Health) -> Leaves {
+        match health {
+            Health::Good => self.good,
+            Health::Moderate => self.moderate,
+            Health::Bad => self.bad,
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct TreeKindTableLoader;
+
+impl AssetLoader for TreeKindTableLoader {
+    fn load<'a>(
+        &'a self,
+        bytes: &'a [u8],
+        load_context: &'a mut LoadContext,
+    ) -> BoxedFuture<'a, Result<(), bevy::asset::Error>> {
+        Box::pin(async move {
+            let table: TreeKindTable = ron::de::from_bytes(bytes)?;
+            load_context.set_default_asset(LoadedAsset::new(table));
+            Ok(())
+        })
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["kinds.ron"]
+    }
+}
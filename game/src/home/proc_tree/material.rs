@@ -0,0 +1,51 @@
+use crate::data::{TreeInfo, MAX_VITALITY};
+use bevy::prelude::*;
+use bevy::reflect::TypeUuid;
+use bevy::render::render_resource::{AsBindGroup, ShaderRef};
+use bevy::sprite::Material2d;
+
+/// How far the crown sways, as a fraction of the texture width.
+pub const WIND_STRENGTH: f32 = 0.015;
+
+#[derive(AsBindGroup, TypeUuid, Clone, Default)]
+#[uuid = "3f0b8d8e-7a61-4c2e-b7f4-1d95c0a6e2b7"]
+pub struct ProcTreeMaterial {
+    /// Seasonal tint multiplied over the texture.
+    #[uniform(0)]
+    pub color: Vec4,
+    /// 0 keeps the texture colors, 1 turns it fully grey.
+    #[uniform(0)]
+    pub desaturation: f32,
+    #[uniform(0)]
+    pub wind: f32,
+    /// Seconds since startup, driving the wind sway.
+    #[uniform(0)]
+    pub time: f32,
+    #[texture(1)]
+    #[sampler(2)]
+    pub texture: Handle<Image>,
+}
+
+impl Material2d for ProcTreeMaterial {
+    fn fragment_shader() -> ShaderRef {
+        "shaders/proc_tree.wgsl".into()
+    }
+}
+
+/// Fades smoothly with vitality, so decay shows before the leaves change.
+pub fn desaturation(tree: &TreeInfo) -> f32 {
+    if tree.is_dead() {
+        return 0.9;
+    }
+    0.75 * (1.0 - tree.vitality / MAX_VITALITY).clamp(0.0, 1.0)
+}
+
+/// Tint for the given month (1 to 12), following the northern hemisphere seasons.
+pub fn season_tint(month: u32) -> Vec4 {
+    match month {
+        3..=5 => Vec4::new(0.95, 1.05, 0.95, 1.0),
+        6..=8 => Vec4::ONE,
+        9..=11 => Vec4::new(1.08, 0.95, 0.82, 1.0),
+        _ => Vec4::new(0.9, 0.95, 1.08, 1.0),
+    }
+}
@@ -1,27 +1,38 @@
-use crate::data::{CurrentQuestInfo, QuestAppearedEvent, QuestCompletedEvent, QuestMissedEvent};
+use super::{cursor_to_world, format_duration, HomeScreen};
+use crate::clock::GameClock;
+use crate::data::{
+    ActiveQuest, CurrentQuestInfo, QuestProgressEvent, QuestRejectedEvent, QuestSkippedEvent,
+    QuestSnoozedEvent,
+};
 use bevy::math::{ivec3, vec2, vec3};
 use bevy::prelude::*;
-use bevy::text::Text2dBounds;
+use bevy::text::{Text2dBounds, Text2dSize};
 use bevy_easings::{custom_ease_system, CustomComponentEase, EaseFunction, EasingType, Lerp};
 use bevy_simple_tilemap::plugin::SimpleTileMapPlugin;
 use bevy_simple_tilemap::prelude::*;
-use chrono::{DateTime, Duration, Local};
 
 pub struct QuestPanelPlugin;
 
 impl Plugin for QuestPanelPlugin {
     fn build(&self, app: &mut App) {
         app.add_plugin(SimpleTileMapPlugin)
+            .init_resource::<ShownQuest>()
+            .add_event::<QuestActionPressed>()
             .add_startup_system(setup_quest_panel)
             .add_system(custom_ease_system::<QuestPanel>)
             .add_system(update_quest_panel_content)
-            .add_system(update_quest_panel_ui)
-            .add_system(handle_quest_events);
+            .add_system_set(
+                SystemSet::on_update(HomeScreen::Home)
+                    .with_system(update_quest_panel_ui)
+                    .with_system(update_quest_action_buttons),
+            )
+            .add_system(apply_quest_actions)
+            .add_system(open_or_close_panel);
     }
 }
 
 #[derive(Clone, Copy, Default, Debug)]
-struct TiledPanel {
+pub(super) struct TiledPanel {
     pub tiled_size: IVec2,
     pub top_left_tile: u32,
     pub top_right_tile: u32,
@@ -32,7 +43,7 @@ struct TiledPanel {
 }
 
 #[derive(Component, Default)]
-struct QuestPanel {
+pub(super) struct QuestPanel {
     pub draggable: bool,
     pub expansion_fraction: f32,
     pub dragged_from: Option<Vec2>,
@@ -50,6 +61,65 @@ struct QuestButton {
     is_pressed: bool,
 }
 
+/// What the buttons on the panel do to the shown quest.
+#[derive(Clone, Copy)]
+enum QuestAction {
+    Progress,
+    Snooze,
+    Skip,
+    Reject,
+    Previous,
+    Next,
+}
+
+impl QuestAction {
+    /// Ways of declining the shown quest, offered above the "done" button.
+    const DECLINES: [QuestAction; 3] =
+        [QuestAction::Snooze, QuestAction::Skip, QuestAction::Reject];
+
+    fn label(self) -> &'static str {
+        match self {
+            QuestAction::Progress => "Done",
+            QuestAction::Snooze => "Snooze",
+            QuestAction::Skip => "Skip",
+            QuestAction::Reject => "Reject",
+            QuestAction::Previous => "<",
+            QuestAction::Next => ">",
+        }
+    }
+}
+
+struct QuestActionPressed(QuestAction);
+
+/// Index into `CurrentQuestInfo::active_quests` of the quest on the panel.
+#[derive(Resource, Default)]
+struct ShownQuest(usize);
+
+impl ShownQuest {
+    fn get<'a>(&self, current_quest_info: &'a CurrentQuestInfo) -> Option<&'a ActiveQuest> {
+        let last = current_quest_info.active_quests.len().checked_sub(1)?;
+        current_quest_info.active_quests.get(self.0.min(last))
+    }
+}
+
+#[derive(Component, Clone, Copy)]
+struct QuestActionButton {
+    action: QuestAction,
+    is_pressed: bool,
+}
+
+impl QuestPanel {
+    /// Whether `pos`, in world space, is on the panel placed by `transform`.
+    pub(super) fn contains(&self, transform: &Transform, pos: Vec2) -> bool {
+        // Tiles are centered on their grid position, with the top row at the origin.
+        let tile_size = 8.0 * transform.scale.x;
+        let half_width = (self.tiles_info.tiled_size.x as f32 + 0.5) * tile_size;
+        let top = transform.translation.y + tile_size / 2.0;
+        let bottom = top - self.tiles_info.tiled_size.y as f32 * tile_size;
+        (pos.x - transform.translation.x).abs() <= half_width && pos.y <= top && pos.y >= bottom
+    }
+}
+
 impl Lerp for QuestPanel {
     type Scalar = f32;
 
@@ -118,7 +188,7 @@ fn generate_panel_tilemap(tiles_info: TiledPanel) -> TileMap {
 
     let mut tilemap = TileMap::default();
     tilemap.set_tiles(tiles);
-    return tilemap;
+    tilemap
 }
 
 fn setup_quest_panel(
@@ -222,6 +292,51 @@ fn setup_quest_panel(
                 })
                 .insert(QuestDescription);
 
+            let pages_x = text_box_width / 2.0 - tile_size * 0.5;
+            for (action, x) in [
+                (QuestAction::Previous, -pages_x),
+                (QuestAction::Next, pages_x),
+            ] {
+                parent
+                    .spawn(Text2dBundle {
+                        text: Text::from_section(action.label(), header_text_style.clone())
+                            .with_alignment(TextAlignment::CENTER),
+                        transform: Transform {
+                            translation: vec3(x, -text_top_margin, 1.0),
+                            rotation: Quat::default(),
+                            scale: Vec3::splat(text_scale),
+                        },
+                        ..default()
+                    })
+                    .insert(QuestActionButton {
+                        action,
+                        is_pressed: false,
+                    });
+            }
+
+            let actions_y = -text_box_height + tile_size * 3.5;
+            for (index, action) in QuestAction::DECLINES.into_iter().enumerate() {
+                parent
+                    .spawn(Text2dBundle {
+                        text: Text::from_section(action.label(), description_text_style.clone())
+                            .with_alignment(TextAlignment::CENTER),
+                        transform: Transform {
+                            translation: vec3(
+                                (index as f32 - 1.0) * text_box_width / 3.0,
+                                actions_y,
+                                1.0,
+                            ),
+                            rotation: Quat::default(),
+                            scale: Vec3::splat(text_scale),
+                        },
+                        ..default()
+                    })
+                    .insert(QuestActionButton {
+                        action,
+                        is_pressed: false,
+                    });
+            }
+
             let button_pos = vec3(0.0, -text_box_height + tile_size * 2.0, 1.0);
             parent
                 .spawn(SpriteSheetBundle {
@@ -233,46 +348,33 @@ fn setup_quest_panel(
         });
 }
 
-fn cursor_to_world(window: &Window, cam_transform: &Transform, cursor_pos: Vec2) -> Vec2 {
-    // get the size of the window
-    let size = Vec2::new(window.width() as f32, window.height() as f32);
-
-    // the default orthographic projection is in pixels from the center;
-    // just undo the translation
-    let screen_pos = cursor_pos - size / 2.0;
-
-    // apply the camera transform
-    let out = cam_transform.compute_matrix() * screen_pos.extend(0.0).extend(1.0);
-    Vec2::new(out.x, out.y)
-}
-
-fn format_duration(duration: Duration) -> String {
-    if duration >= Duration::days(365) {
-        let years = duration.num_days() / 365;
-        years.to_string() + " years"
-    } else if duration >= Duration::weeks(1) {
-        duration.num_weeks().to_string() + "w"
-    } else if duration >= Duration::days(1) {
-        duration.num_days().to_string() + "d"
-    } else if duration >= Duration::hours(1) {
-        duration.num_days().to_string() + "h"
-    } else {
-        duration.num_seconds().max(0).to_string() + "s"
-    }
-}
-
 fn update_quest_panel_content(
     mut headers: Query<&mut Text, (With<QuestHeader>, Without<QuestDescription>)>,
     mut descriptions: Query<&mut Text, (With<QuestDescription>, Without<QuestHeader>)>,
     current_quest: Res<CurrentQuestInfo>,
+    shown_quest: Res<ShownQuest>,
+    clock: Res<GameClock>,
 ) {
     let mut header_text = headers.single_mut();
     let mut description_text = descriptions.single_mut();
 
-    if let Some(quest) = current_quest.current_quest.as_ref() {
-        let time_remaining = quest.deadline - DateTime::from(Local::now());
-        let title = quest.quest.name.clone();
-        let description = quest.quest.description.clone();
+    if let Some(quest) = shown_quest.get(&current_quest) {
+        let time_remaining = quest.deadline - clock.now();
+        let mut title = quest.quest.name.clone();
+        let quest_count = current_quest.active_quests.len();
+        if quest_count > 1 {
+            let index = shown_quest.0.min(quest_count - 1);
+            title = format!("{} ({}/{})", title, index + 1, quest_count);
+        }
+        let description = format!(
+            "{} {} quest: +{:.0} / -{:.0}\n\n{}{}",
+            quest.quest.difficulty,
+            quest.quest.category,
+            quest.quest.scaled_reward(),
+            quest.quest.scaled_penalty(),
+            quest.quest.description,
+            format_progress(quest)
+        );
 
         header_text.sections.first_mut().unwrap().value =
             title + " in " + format_duration(time_remaining).as_str();
@@ -280,23 +382,44 @@ fn update_quest_panel_content(
     }
 }
 
-fn handle_quest_events(
+/// A checklist of the quest's steps, or a count for quests with a bare target.
+fn format_progress(quest: &ActiveQuest) -> String {
+    if !quest.quest.steps.is_empty() {
+        let checklist: String = quest
+            .quest
+            .steps
+            .iter()
+            .enumerate()
+            .map(|(index, step)| {
+                let mark = if index < quest.progress as usize {
+                    'x'
+                } else {
+                    ' '
+                };
+                format!("\n[{}] {}", mark, step)
+            })
+            .collect();
+        format!("\n{}", checklist)
+    } else if quest.quest.target > 1 {
+        format!("\n\nDone {} of {}", quest.progress, quest.quest.target)
+    } else {
+        String::new()
+    }
+}
+
+/// Slides the panel in while there are quests to show and out once they are all gone.
+fn open_or_close_panel(
     mut commands: Commands,
     mut panel: Query<(Entity, &mut QuestPanel)>,
-    quest_completed_events: EventReader<QuestCompletedEvent>,
-    quest_missed_events: EventReader<QuestMissedEvent>,
-    quest_appeared_events: EventReader<QuestAppearedEvent>,
+    current_quest: Res<CurrentQuestInfo>,
 ) {
     let (panel_entity, mut panel) = panel.single_mut();
+    let has_quests = !current_quest.active_quests.is_empty();
 
-    let quest_completed = !quest_completed_events.is_empty();
-    let quest_missed = !quest_missed_events.is_empty();
-    let quest_appeared = !quest_appeared_events.is_empty();
-
-    if quest_appeared {
+    if has_quests && !panel.draggable {
         panel.draggable = true;
         animate_panel(&mut commands, panel_entity, &panel, -0.2, 0.0);
-    } else if quest_completed || quest_missed {
+    } else if !has_quests && panel.draggable {
         panel.draggable = false;
         animate_panel(
             &mut commands,
@@ -340,10 +463,10 @@ fn panel_y_from_expansion_fraction(
     expansion_fraction: f32,
 ) -> f32 {
     let add_top_margin = tile_height * 0.5;
-    return (panel_height - tile_height - add_top_margin) * expansion_fraction
+    (panel_height - tile_height - add_top_margin) * expansion_fraction
         + add_top_margin
         + tile_height * 0.5
-        - window_height * 0.5;
+        - window_height * 0.5
 }
 
 fn expansion_fraction_from_panel_y(
@@ -353,8 +476,8 @@ fn expansion_fraction_from_panel_y(
     panel_y: f32,
 ) -> f32 {
     let add_top_margin = tile_height * 0.5;
-    return (panel_y + window_height * 0.5 - tile_height * 0.5 - add_top_margin)
-        / (panel_height - tile_height - add_top_margin);
+    (panel_y + window_height * 0.5 - tile_height * 0.5 - add_top_margin)
+        / (panel_height - tile_height - add_top_margin)
 }
 
 fn update_quest_panel_ui(
@@ -363,7 +486,7 @@ fn update_quest_panel_ui(
     mut buttons: Query<(&GlobalTransform, &mut QuestButton, &mut TextureAtlasSprite)>,
     windows: Res<Windows>,
     mouse_buttons: Res<Input<MouseButton>>,
-    mut quest_completed_events: EventWriter<QuestCompletedEvent>,
+    mut quest_action_events: EventWriter<QuestActionPressed>,
 ) {
     let window = windows.get_primary().unwrap();
     let cam_transform = cameras.single();
@@ -446,11 +569,88 @@ fn update_quest_panel_ui(
         panel.dragged_from = None;
     }
 
-    if mouse_buttons.just_released(MouseButton::Left) {
-        if button.is_pressed {
+    if mouse_buttons.just_released(MouseButton::Left) && button.is_pressed {
+        button.is_pressed = false;
+        button_sprite.index = 0;
+        quest_action_events.send(QuestActionPressed(QuestAction::Progress));
+    }
+}
+
+fn update_quest_action_buttons(
+    mut buttons: Query<(
+        &GlobalTransform,
+        &Text2dSize,
+        &mut QuestActionButton,
+        &mut Text,
+    )>,
+    cameras: Query<&Transform, With<Camera>>,
+    windows: Res<Windows>,
+    mouse_buttons: Res<Input<MouseButton>>,
+    mut quest_action_events: EventWriter<QuestActionPressed>,
+) {
+    let window = windows.get_primary().unwrap();
+    let cursor_pos = window
+        .cursor_position()
+        .map(|cursor_pos| cursor_to_world(window, cameras.single(), cursor_pos));
+
+    for (global_transform, text_size, mut button, mut text) in buttons.iter_mut() {
+        let (scale, _, translation) = global_transform.to_scale_rotation_translation();
+        let half_size = text_size.size * scale.truncate() / 2.0;
+        let hovered = cursor_pos.is_some_and(|cursor_pos| {
+            (cursor_pos - translation.truncate())
+                .abs()
+                .cmple(half_size)
+                .all()
+        });
+
+        if mouse_buttons.just_pressed(MouseButton::Left) && hovered {
+            button.is_pressed = true;
+        }
+        if mouse_buttons.just_released(MouseButton::Left) && button.is_pressed {
             button.is_pressed = false;
-            button_sprite.index = 0;
-            quest_completed_events.send(QuestCompletedEvent);
+            if hovered {
+                quest_action_events.send(QuestActionPressed(button.action));
+            }
+        }
+
+        let color = if button.is_pressed {
+            Color::GRAY
+        } else {
+            Color::BLACK
+        };
+        if text.sections[0].style.color != color {
+            text.sections[0].style.color = color;
+        }
+    }
+}
+
+/// Turns button presses into events about the shown quest.
+fn apply_quest_actions(
+    mut quest_action_events: EventReader<QuestActionPressed>,
+    current_quest: Res<CurrentQuestInfo>,
+    mut shown_quest: ResMut<ShownQuest>,
+    mut quest_progress_events: EventWriter<QuestProgressEvent>,
+    mut quest_snoozed_events: EventWriter<QuestSnoozedEvent>,
+    mut quest_skipped_events: EventWriter<QuestSkippedEvent>,
+    mut quest_rejected_events: EventWriter<QuestRejectedEvent>,
+) {
+    let quest_count = current_quest.active_quests.len();
+    for QuestActionPressed(action) in quest_action_events.iter() {
+        let Some(shown) = shown_quest.get(&current_quest) else {
+            continue;
+        };
+        let quest_id = shown.quest.id.clone();
+        match action {
+            QuestAction::Progress => quest_progress_events.send(QuestProgressEvent { quest_id }),
+            QuestAction::Snooze => quest_snoozed_events.send(QuestSnoozedEvent { quest_id }),
+            QuestAction::Skip => quest_skipped_events.send(QuestSkippedEvent { quest_id }),
+            QuestAction::Reject => quest_rejected_events.send(QuestRejectedEvent { quest_id }),
+            QuestAction::Previous => {
+                shown_quest.0 = (shown_quest.0.min(quest_count - 1) + quest_count - 1) % quest_count
+            }
+            QuestAction::Next => {
+                shown_quest.0 = (shown_quest.0.min(quest_count - 1) + 1) % quest_count
+            }
         }
     }
 }
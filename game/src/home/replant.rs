@@ -0,0 +1,150 @@
+use super::{format_duration, switch_screen, HomeScreen};
+use crate::data::{AwaySummary, CurrentTree, Memorial, ReplantTreeEvent, TreeInfo};
+use bevy::prelude::*;
+
+pub struct ReplantPlugin;
+
+impl Plugin for ReplantPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_system_set(SystemSet::on_update(HomeScreen::Home).with_system(open_replant_screen))
+            .add_system_set(
+                SystemSet::on_enter(HomeScreen::Replant).with_system(setup_replant_screen),
+            )
+            .add_system_set(
+                SystemSet::on_update(HomeScreen::Replant)
+                    .with_system(press_replant_button)
+                    .with_system(close_replant_screen),
+            )
+            .add_system_set(
+                SystemSet::on_exit(HomeScreen::Replant).with_system(cleanup_replant_screen),
+            );
+    }
+}
+
+#[derive(Component)]
+struct ReplantRoot;
+
+#[derive(Component)]
+struct ReplantButton;
+
+fn selected_tree_is_dead(current_tree: &CurrentTree, trees: &Query<&TreeInfo>) -> bool {
+    trees.get(current_tree.0).is_ok_and(|tree| tree.is_dead())
+}
+
+fn open_replant_screen(
+    current_tree: Res<CurrentTree>,
+    trees: Query<&TreeInfo>,
+    summary: Option<Res<AwaySummary>>,
+    mut screen: ResMut<State<HomeScreen>>,
+) {
+    // The away summary goes first, it explains how the tree died.
+    if summary.is_none() && selected_tree_is_dead(&current_tree, &trees) {
+        switch_screen(&mut screen, HomeScreen::Replant);
+    }
+}
+
+fn setup_replant_screen(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    current_tree: Res<CurrentTree>,
+    trees: Query<&TreeInfo>,
+    memorial: Res<Memorial>,
+) {
+    let Ok(tree) = trees.get(current_tree.0) else {
+        return;
+    };
+    let font = asset_server.load("fonts/at01.ttf");
+    let header_text_style = TextStyle {
+        font: font.clone(),
+        font_size: 48.0,
+        color: Color::BLACK,
+    };
+    let text_style = TextStyle {
+        font,
+        font_size: 32.0,
+        color: Color::DARK_GRAY,
+    };
+
+    let mut lines = Vec::new();
+    if let Some(died_at) = tree.died_at {
+        lines.push(format!(
+            "It lived for {}",
+            format_duration(tree.growth.age(died_at))
+        ));
+    }
+    if !memorial.entries.is_empty() {
+        lines.push("In memory of:".to_string());
+        lines.extend(memorial.entries.iter().map(|entry| entry.name.clone()));
+    }
+
+    commands
+        .spawn(NodeBundle {
+            style: Style {
+                size: Size::new(Val::Percent(100.0), Val::Percent(100.0)),
+                position_type: PositionType::Absolute,
+                flex_direction: FlexDirection::Column,
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                ..default()
+            },
+            background_color: Color::rgba(0.85, 0.82, 0.78, 0.85).into(),
+            z_index: ZIndex::Global(10),
+            ..default()
+        })
+        .insert(ReplantRoot)
+        .with_children(|parent| {
+            parent.spawn(TextBundle::from_section(
+                format!("{} has withered", tree.name),
+                header_text_style.clone(),
+            ));
+            for line in lines {
+                parent.spawn(TextBundle::from_section(line, text_style.clone()));
+            }
+            parent
+                .spawn(ButtonBundle {
+                    style: Style {
+                        margin: UiRect::top(Val::Px(24.0)),
+                        padding: UiRect::all(Val::Px(8.0)),
+                        ..default()
+                    },
+                    background_color: Color::rgb(0.6, 0.8, 0.5).into(),
+                    ..default()
+                })
+                .insert(ReplantButton)
+                .with_children(|parent| {
+                    parent.spawn(TextBundle::from_section(
+                        "Plant a new tree",
+                        header_text_style,
+                    ));
+                });
+        });
+}
+
+fn press_replant_button(
+    buttons: Query<&Interaction, (Changed<Interaction>, With<ReplantButton>)>,
+    mut replant_tree_events: EventWriter<ReplantTreeEvent>,
+) {
+    if buttons
+        .iter()
+        .any(|interaction| *interaction == Interaction::Clicked)
+    {
+        replant_tree_events.send(ReplantTreeEvent);
+    }
+}
+
+/// Leaves once the new tree is in, or another living tree was selected meanwhile.
+fn close_replant_screen(
+    current_tree: Res<CurrentTree>,
+    trees: Query<&TreeInfo>,
+    mut screen: ResMut<State<HomeScreen>>,
+) {
+    if !selected_tree_is_dead(&current_tree, &trees) {
+        switch_screen(&mut screen, HomeScreen::Home);
+    }
+}
+
+fn cleanup_replant_screen(mut commands: Commands, roots: Query<Entity, With<ReplantRoot>>) {
+    for root in roots.iter() {
+        commands.entity(root).despawn_recursive();
+    }
+}
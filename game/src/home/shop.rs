@@ -0,0 +1,228 @@
+use super::{switch_screen, HomeScreen};
+use crate::data::{BuyCosmeticEvent, ToggleCosmeticEvent, Wallet, Wardrobe, COSMETICS};
+use bevy::prelude::*;
+
+pub struct ShopPlugin;
+
+impl Plugin for ShopPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_startup_system(setup_shop_button)
+            .add_system_set(SystemSet::on_update(HomeScreen::Home).with_system(press_shop_button))
+            .add_system_set(SystemSet::on_enter(HomeScreen::Shop).with_system(setup_shop_screen))
+            .add_system_set(
+                SystemSet::on_update(HomeScreen::Shop)
+                    .with_system(press_shop_item)
+                    .with_system(refresh_shop_screen)
+                    .with_system(close_shop_screen),
+            )
+            .add_system_set(SystemSet::on_exit(HomeScreen::Shop).with_system(cleanup_shop_screen));
+    }
+}
+
+#[derive(Component)]
+struct ShopButton;
+
+#[derive(Component)]
+struct ShopRoot;
+
+#[derive(Component)]
+struct ShopItemButton {
+    id: &'static str,
+}
+
+#[derive(Component)]
+struct ShopCloseButton;
+
+fn setup_shop_button(mut commands: Commands, asset_server: Res<AssetServer>) {
+    let text_style = TextStyle {
+        font: asset_server.load("fonts/at01.ttf"),
+        font_size: 32.0,
+        color: Color::BLACK,
+    };
+
+    commands
+        .spawn(ButtonBundle {
+            style: Style {
+                position_type: PositionType::Absolute,
+                position: UiRect {
+                    top: Val::Px(72.0),
+                    left: Val::Px(16.0),
+                    ..default()
+                },
+                padding: UiRect::all(Val::Px(8.0)),
+                ..default()
+            },
+            background_color: Color::rgb(0.8, 0.8, 0.7).into(),
+            ..default()
+        })
+        .insert(ShopButton)
+        .with_children(|parent| {
+            parent.spawn(TextBundle::from_section("Shop", text_style));
+        });
+}
+
+fn press_shop_button(
+    buttons: Query<&Interaction, (Changed<Interaction>, With<ShopButton>)>,
+    mut screen: ResMut<State<HomeScreen>>,
+) {
+    if buttons
+        .iter()
+        .any(|interaction| *interaction == Interaction::Clicked)
+    {
+        switch_screen(&mut screen, HomeScreen::Shop);
+    }
+}
+
+fn setup_shop_screen(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    wallet: Res<Wallet>,
+    wardrobe: Res<Wardrobe>,
+) {
+    spawn_shop_screen(&mut commands, &asset_server, &wallet, &wardrobe);
+}
+
+fn spawn_shop_screen(
+    commands: &mut Commands,
+    asset_server: &AssetServer,
+    wallet: &Wallet,
+    wardrobe: &Wardrobe,
+) {
+    let font = asset_server.load("fonts/at01.ttf");
+    let header_text_style = TextStyle {
+        font: font.clone(),
+        font_size: 48.0,
+        color: Color::BLACK,
+    };
+    let text_style = TextStyle {
+        font,
+        font_size: 32.0,
+        color: Color::DARK_GRAY,
+    };
+
+    commands
+        .spawn(NodeBundle {
+            style: Style {
+                size: Size::new(Val::Percent(100.0), Val::Percent(100.0)),
+                position_type: PositionType::Absolute,
+                flex_direction: FlexDirection::Column,
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                ..default()
+            },
+            background_color: Color::rgba(1.0, 1.0, 1.0, 0.9).into(),
+            z_index: ZIndex::Global(10),
+            ..default()
+        })
+        .insert(ShopRoot)
+        .with_children(|parent| {
+            parent.spawn(TextBundle::from_section("Shop", header_text_style.clone()));
+            parent.spawn(TextBundle::from_section(
+                format!("{} leaves", wallet.leaves),
+                text_style.clone(),
+            ));
+            for cosmetic in &COSMETICS {
+                let (label, background_color) = if wardrobe.is_equipped(cosmetic) {
+                    ("worn".to_string(), Color::rgb(0.6, 0.8, 0.5))
+                } else if wardrobe.owns(cosmetic.id) {
+                    ("owned".to_string(), Color::rgb(0.8, 0.8, 0.7))
+                } else if wallet.leaves >= cosmetic.price {
+                    (
+                        format!("{} leaves", cosmetic.price),
+                        Color::rgb(0.95, 0.85, 0.45),
+                    )
+                } else {
+                    (
+                        format!("{} leaves", cosmetic.price),
+                        Color::rgb(0.7, 0.7, 0.7),
+                    )
+                };
+                parent
+                    .spawn(ButtonBundle {
+                        style: Style {
+                            margin: UiRect::top(Val::Px(8.0)),
+                            padding: UiRect::all(Val::Px(6.0)),
+                            ..default()
+                        },
+                        background_color: background_color.into(),
+                        ..default()
+                    })
+                    .insert(ShopItemButton { id: cosmetic.id })
+                    .with_children(|parent| {
+                        parent.spawn(TextBundle::from_section(
+                            format!("{:?}: {} - {}", cosmetic.slot, cosmetic.name, label),
+                            text_style.clone(),
+                        ));
+                    });
+            }
+            parent
+                .spawn(ButtonBundle {
+                    style: Style {
+                        margin: UiRect::top(Val::Px(24.0)),
+                        padding: UiRect::all(Val::Px(8.0)),
+                        ..default()
+                    },
+                    background_color: Color::rgb(0.6, 0.8, 0.5).into(),
+                    ..default()
+                })
+                .insert(ShopCloseButton)
+                .with_children(|parent| {
+                    parent.spawn(TextBundle::from_section("Back", header_text_style));
+                });
+        });
+}
+
+/// Buys the pressed cosmetic, or puts it on or off once it is owned.
+fn press_shop_item(
+    buttons: Query<(&Interaction, &ShopItemButton), Changed<Interaction>>,
+    wardrobe: Res<Wardrobe>,
+    mut buy_cosmetic_events: EventWriter<BuyCosmeticEvent>,
+    mut toggle_cosmetic_events: EventWriter<ToggleCosmeticEvent>,
+) {
+    for (interaction, button) in buttons.iter() {
+        if *interaction != Interaction::Clicked {
+            continue;
+        }
+        let id = button.id.to_string();
+        if wardrobe.owns(button.id) {
+            toggle_cosmetic_events.send(ToggleCosmeticEvent { id });
+        } else {
+            buy_cosmetic_events.send(BuyCosmeticEvent { id });
+        }
+    }
+}
+
+/// Rebuilds the screen once a purchase or change of cosmetics went through.
+fn refresh_shop_screen(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    wallet: Res<Wallet>,
+    wardrobe: Res<Wardrobe>,
+    roots: Query<Entity, With<ShopRoot>>,
+) {
+    if !wallet.is_changed() && !wardrobe.is_changed() {
+        return;
+    }
+    for root in roots.iter() {
+        commands.entity(root).despawn_recursive();
+    }
+    spawn_shop_screen(&mut commands, &asset_server, &wallet, &wardrobe);
+}
+
+fn close_shop_screen(
+    buttons: Query<&Interaction, (Changed<Interaction>, With<ShopCloseButton>)>,
+    mut screen: ResMut<State<HomeScreen>>,
+) {
+    if buttons
+        .iter()
+        .any(|interaction| *interaction == Interaction::Clicked)
+    {
+        switch_screen(&mut screen, HomeScreen::Home);
+    }
+}
+
+fn cleanup_shop_screen(mut commands: Commands, roots: Query<Entity, With<ShopRoot>>) {
+    for root in roots.iter() {
+        commands.entity(root).despawn_recursive();
+    }
+}
@@ -0,0 +1,237 @@
+use super::{format_duration, switch_screen, HomeScreen};
+use crate::clock::GameClock;
+use crate::data::{Achievements, Profile, QuestHistory, Streak, ACHIEVEMENTS};
+use bevy::prelude::*;
+
+/// Weeks shown on the completions chart.
+const CHART_WEEKS: usize = 8;
+
+const CHART_HEIGHT: f32 = 160.0;
+
+pub struct StatsPlugin;
+
+impl Plugin for StatsPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_startup_system(setup_stats_button)
+            .add_system_set(SystemSet::on_update(HomeScreen::Home).with_system(press_stats_button))
+            .add_system_set(SystemSet::on_enter(HomeScreen::Stats).with_system(setup_stats_screen))
+            .add_system_set(SystemSet::on_update(HomeScreen::Stats).with_system(close_stats_screen))
+            .add_system_set(
+                SystemSet::on_exit(HomeScreen::Stats).with_system(cleanup_stats_screen),
+            );
+    }
+}
+
+#[derive(Component)]
+struct StatsButton;
+
+#[derive(Component)]
+struct StatsRoot;
+
+#[derive(Component)]
+struct StatsCloseButton;
+
+fn setup_stats_button(mut commands: Commands, asset_server: Res<AssetServer>) {
+    let text_style = TextStyle {
+        font: asset_server.load("fonts/at01.ttf"),
+        font_size: 32.0,
+        color: Color::BLACK,
+    };
+
+    commands
+        .spawn(ButtonBundle {
+            style: Style {
+                position_type: PositionType::Absolute,
+                position: UiRect {
+                    top: Val::Px(16.0),
+                    left: Val::Px(16.0),
+                    ..default()
+                },
+                padding: UiRect::all(Val::Px(8.0)),
+                ..default()
+            },
+            background_color: Color::rgb(0.8, 0.8, 0.7).into(),
+            ..default()
+        })
+        .insert(StatsButton)
+        .with_children(|parent| {
+            parent.spawn(TextBundle::from_section("Stats", text_style));
+        });
+}
+
+fn press_stats_button(
+    buttons: Query<&Interaction, (Changed<Interaction>, With<StatsButton>)>,
+    mut screen: ResMut<State<HomeScreen>>,
+) {
+    if buttons
+        .iter()
+        .any(|interaction| *interaction == Interaction::Clicked)
+    {
+        switch_screen(&mut screen, HomeScreen::Stats);
+    }
+}
+
+fn setup_stats_screen(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    history: Res<QuestHistory>,
+    streak: Res<Streak>,
+    achievements: Res<Achievements>,
+    profile: Res<Profile>,
+    clock: Res<GameClock>,
+) {
+    let font = asset_server.load("fonts/at01.ttf");
+    let header_text_style = TextStyle {
+        font: font.clone(),
+        font_size: 48.0,
+        color: Color::BLACK,
+    };
+    let text_style = TextStyle {
+        font,
+        font_size: 32.0,
+        color: Color::DARK_GRAY,
+    };
+
+    let (level_xp, level_xp_needed) = profile.level_progress();
+    let completed = history.completed().count();
+    let mut lines = vec![format!(
+        "Level {} ({} / {} XP)",
+        profile.level(),
+        level_xp,
+        level_xp_needed
+    )];
+    lines.push(match history.completion_rate() {
+        Some(rate) => format!(
+            "Completion rate: {:.0}% ({} completed)",
+            rate * 100.0,
+            completed
+        ),
+        None => "No quests finished yet".to_string(),
+    });
+    lines.push(format!(
+        "Streak: {} (best {}), {} freezes left",
+        streak.current, streak.best, streak.freezes
+    ));
+    if let Some(average) = history.average_time_taken() {
+        lines.push(format!("Average time taken: {}", format_duration(average)));
+    }
+    let best_categories: Vec<String> = history
+        .best_categories()
+        .iter()
+        .take(3)
+        .map(|(category, completions)| format!("{} ({})", category, completions))
+        .collect();
+    if !best_categories.is_empty() {
+        lines.push(format!("Best categories: {}", best_categories.join(", ")));
+    }
+
+    lines.push(format!(
+        "Achievements: {} of {}",
+        achievements.unlocked.len(),
+        ACHIEVEMENTS.len()
+    ));
+
+    let weekly_completions = history.weekly_completions(clock.now(), CHART_WEEKS);
+    let most_in_a_week = weekly_completions.iter().copied().max().unwrap_or(0).max(1);
+
+    commands
+        .spawn(NodeBundle {
+            style: Style {
+                size: Size::new(Val::Percent(100.0), Val::Percent(100.0)),
+                position_type: PositionType::Absolute,
+                flex_direction: FlexDirection::Column,
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                ..default()
+            },
+            background_color: Color::rgba(1.0, 1.0, 1.0, 0.9).into(),
+            z_index: ZIndex::Global(10),
+            ..default()
+        })
+        .insert(StatsRoot)
+        .with_children(|parent| {
+            parent.spawn(TextBundle::from_section(
+                "Statistics",
+                header_text_style.clone(),
+            ));
+            for line in lines {
+                parent.spawn(TextBundle::from_section(line, text_style.clone()));
+            }
+            parent.spawn(TextBundle::from_section(
+                format!("Completions over the last {} weeks", CHART_WEEKS),
+                text_style.clone(),
+            ));
+            // One bar per week, oldest on the left, scaled to the busiest week.
+            parent
+                .spawn(NodeBundle {
+                    style: Style {
+                        size: Size::new(Val::Auto, Val::Px(CHART_HEIGHT)),
+                        align_items: AlignItems::FlexEnd,
+                        margin: UiRect::top(Val::Px(8.0)),
+                        ..default()
+                    },
+                    ..default()
+                })
+                .with_children(|parent| {
+                    for completions in weekly_completions {
+                        let height = CHART_HEIGHT * completions as f32 / most_in_a_week as f32;
+                        parent
+                            .spawn(NodeBundle {
+                                style: Style {
+                                    flex_direction: FlexDirection::Column,
+                                    align_items: AlignItems::Center,
+                                    margin: UiRect::horizontal(Val::Px(4.0)),
+                                    ..default()
+                                },
+                                ..default()
+                            })
+                            .with_children(|parent| {
+                                parent.spawn(TextBundle::from_section(
+                                    completions.to_string(),
+                                    text_style.clone(),
+                                ));
+                                parent.spawn(NodeBundle {
+                                    style: Style {
+                                        size: Size::new(Val::Px(24.0), Val::Px(height)),
+                                        ..default()
+                                    },
+                                    background_color: Color::rgb(0.6, 0.8, 0.5).into(),
+                                    ..default()
+                                });
+                            });
+                    }
+                });
+            parent
+                .spawn(ButtonBundle {
+                    style: Style {
+                        margin: UiRect::top(Val::Px(24.0)),
+                        padding: UiRect::all(Val::Px(8.0)),
+                        ..default()
+                    },
+                    background_color: Color::rgb(0.6, 0.8, 0.5).into(),
+                    ..default()
+                })
+                .insert(StatsCloseButton)
+                .with_children(|parent| {
+                    parent.spawn(TextBundle::from_section("Back", header_text_style));
+                });
+        });
+}
+
+fn close_stats_screen(
+    buttons: Query<&Interaction, (Changed<Interaction>, With<StatsCloseButton>)>,
+    mut screen: ResMut<State<HomeScreen>>,
+) {
+    if buttons
+        .iter()
+        .any(|interaction| *interaction == Interaction::Clicked)
+    {
+        switch_screen(&mut screen, HomeScreen::Home);
+    }
+}
+
+fn cleanup_stats_screen(mut commands: Commands, roots: Query<Entity, With<StatsRoot>>) {
+    for root in roots.iter() {
+        commands.entity(root).despawn_recursive();
+    }
+}
@@ -1,2 +1,4 @@
+pub mod clock;
 pub mod data;
 pub mod home;
+pub mod save;
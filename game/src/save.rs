@@ -0,0 +1,290 @@
+use crate::clock::GameClock;
+use crate::data::{
+    catch_up, load_quest_catalog, Achievements, AwaySummary, CurrentQuestInfo, CurrentTree,
+    DeathRules, Memorial, Profile, QuestAppearedEvent, QuestCatalog, QuestHistory, QuestPool,
+    QuestScheduler, QuestSelector, Streak, TreeInfo, TreeItem, Wallet, Wardrobe,
+};
+use bevy::app::{App, Plugin};
+use bevy::ecs::system::SystemParam;
+use bevy::prelude::*;
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use std::marker::PhantomData;
+
+mod storage;
+
+/// Rewrite the save at least this often, so `saved_at` tracks when the game was closed.
+const HEARTBEAT_SECS: i64 = 60;
+
+pub struct SavePlugin;
+
+impl Plugin for SavePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<SaveLocation>()
+            .add_startup_system_to_stage(StartupStage::PreStartup, restore_save_game)
+            // Its resources are in place by the time `CoreStage::Update` runs the quests.
+            .add_system_to_stage(
+                CoreStage::PreUpdate,
+                resume_save_game.after(load_quest_catalog),
+            )
+            .add_system_to_stage(CoreStage::Last, write_save_game);
+    }
+}
+
+/// Where the save game is kept: a file path on native, a `localStorage` key on the web.
+#[derive(Resource, Clone)]
+pub struct SaveLocation(pub String);
+
+impl Default for SaveLocation {
+    fn default() -> Self {
+        SaveLocation(storage::DEFAULT_LOCATION.to_string())
+    }
+}
+
+#[derive(Default, Serialize, Deserialize)]
+pub struct SaveGame {
+    #[serde(default)]
+    pub saved_at: Option<DateTime<Utc>>,
+    pub trees: Vec<TreeInfo>,
+    /// Index into `trees` of the selected tree.
+    pub selected: usize,
+    pub current_quest_info: CurrentQuestInfo,
+    pub quest_pool: QuestPool,
+    #[serde(default)]
+    pub memorial: Memorial,
+    #[serde(default)]
+    pub scheduler: QuestScheduler,
+    #[serde(default)]
+    pub history: QuestHistory,
+    #[serde(default)]
+    pub streak: Streak,
+    #[serde(default)]
+    pub achievements: Achievements,
+    #[serde(default)]
+    pub wallet: Wallet,
+    #[serde(default)]
+    pub wardrobe: Wardrobe,
+    #[serde(default)]
+    pub profile: Profile,
+}
+
+impl SaveGame {
+    pub fn write(&self, location: &SaveLocation) -> Result<(), String> {
+        let contents = ron::to_string(self).map_err(|err| err.to_string())?;
+        storage::write(&location.0, &contents)
+    }
+}
+
+/// A save game read on startup, waiting for the quest files its quests are looked up in.
+#[derive(Resource)]
+struct PendingSaveGame {
+    save_game: Option<SaveGame>,
+    /// Reserved for the selected tree, so no default tree is planted meanwhile.
+    selected_tree: Entity,
+}
+
+fn restore_save_game(mut commands: Commands, location: Res<SaveLocation>) {
+    let Some(contents) = storage::read(&location.0) else {
+        return;
+    };
+    let save_game: SaveGame = match ron::from_str(&contents) {
+        Ok(save_game) => save_game,
+        Err(err) => {
+            error!("Discarding unreadable save game: {}", err);
+            return;
+        }
+    };
+    if save_game.selected >= save_game.trees.len() {
+        error!("Discarding save game without a selected tree");
+        return;
+    }
+
+    let selected_tree = commands.spawn_empty().id();
+    commands.insert_resource(CurrentTree(selected_tree));
+    commands.insert_resource(PendingSaveGame {
+        save_game: Some(save_game),
+        selected_tree,
+    });
+}
+
+/// What the quests of a save game are resumed against.
+#[derive(SystemParam)]
+struct QuestRules<'w, 's> {
+    catalog: Res<'w, QuestCatalog>,
+    scheduler: ResMut<'w, QuestScheduler>,
+    selector: ResMut<'w, QuestSelector>,
+    death_rules: Res<'w, DeathRules>,
+    #[system_param(ignore)]
+    _marker: PhantomData<&'s ()>,
+}
+
+/// Brings the pending save game up to date once the quest files have loaded, catching up
+/// on the time the game was closed.
+fn resume_save_game(
+    mut commands: Commands,
+    pending: Option<ResMut<PendingSaveGame>>,
+    mut rules: QuestRules,
+    mut quest_appeared_events: EventWriter<QuestAppearedEvent>,
+    clock: Res<GameClock>,
+) {
+    let Some(mut pending) = pending else {
+        return;
+    };
+    if !rules.catalog.loaded {
+        return;
+    }
+    let Some(mut save_game) = pending.save_game.take() else {
+        return;
+    };
+    commands.remove_resource::<PendingSaveGame>();
+
+    rules
+        .catalog
+        .resolve(&mut save_game.current_quest_info, &mut save_game.quest_pool);
+    rules.scheduler.last_enqueued = save_game.scheduler.last_enqueued;
+
+    let selected_tree = &mut save_game.trees[save_game.selected];
+    let now = clock.now();
+    let health_before = selected_tree.health();
+    let missed_quests = catch_up(
+        now,
+        &mut save_game.current_quest_info,
+        &mut save_game.quest_pool,
+        selected_tree,
+        &rules.death_rules,
+        &mut rules.scheduler,
+        &mut rules.selector,
+    );
+    if !missed_quests.is_empty() {
+        commands.insert_resource(AwaySummary {
+            away_for: save_game
+                .saved_at
+                .map_or_else(Duration::zero, |saved_at| now - saved_at),
+            missed_quests: missed_quests
+                .iter()
+                .map(|record| record.name.clone())
+                .collect(),
+            health_before,
+            health_after: selected_tree.health(),
+        });
+    }
+    for _ in &missed_quests {
+        save_game.streak.miss();
+    }
+    save_game.history.records.extend(missed_quests);
+
+    for (index, info) in save_game.trees.into_iter().enumerate() {
+        if index == save_game.selected {
+            commands
+                .entity(pending.selected_tree)
+                .insert(TreeItem { info });
+        } else {
+            commands.spawn(TreeItem { info });
+        }
+    }
+    for active_quest in &save_game.current_quest_info.active_quests {
+        quest_appeared_events.send(QuestAppearedEvent {
+            quest_id: active_quest.quest.id.clone(),
+            tree: pending.selected_tree,
+            at: active_quest.appeared_at,
+            deadline: active_quest.deadline,
+        });
+    }
+    commands.insert_resource(save_game.current_quest_info);
+    commands.insert_resource(save_game.quest_pool);
+    commands.insert_resource(save_game.memorial);
+    commands.insert_resource(save_game.history);
+    commands.insert_resource(save_game.streak);
+    commands.insert_resource(save_game.achievements);
+    commands.insert_resource(save_game.wallet);
+    commands.insert_resource(save_game.wardrobe);
+    commands.insert_resource(save_game.profile);
+}
+
+/// Everything that goes into the save game.
+#[derive(SystemParam)]
+struct SavedState<'w, 's> {
+    current_quest_info: Res<'w, CurrentQuestInfo>,
+    quest_pool: Res<'w, QuestPool>,
+    memorial: Res<'w, Memorial>,
+    scheduler: Res<'w, QuestScheduler>,
+    history: Res<'w, QuestHistory>,
+    streak: Res<'w, Streak>,
+    achievements: Res<'w, Achievements>,
+    wallet: Res<'w, Wallet>,
+    wardrobe: Res<'w, Wardrobe>,
+    profile: Res<'w, Profile>,
+    current_tree: Res<'w, CurrentTree>,
+    trees: Query<'w, 's, (Entity, &'static TreeInfo, ChangeTrackers<TreeInfo>)>,
+}
+
+impl SavedState<'_, '_> {
+    fn is_changed(&self) -> bool {
+        self.current_quest_info.is_changed()
+            || self.quest_pool.is_changed()
+            || self.memorial.is_changed()
+            || self.scheduler.is_changed()
+            || self.history.is_changed()
+            || self.streak.is_changed()
+            || self.achievements.is_changed()
+            || self.wallet.is_changed()
+            || self.wardrobe.is_changed()
+            || self.profile.is_changed()
+            || self.current_tree.is_changed()
+            || self
+                .trees
+                .iter()
+                .any(|(_, _, tracker)| tracker.is_changed())
+    }
+
+    fn to_save_game(&self, now: DateTime<Utc>) -> Option<SaveGame> {
+        let mut trees: Vec<(Entity, &TreeInfo)> = self
+            .trees
+            .iter()
+            .map(|(entity, tree, _)| (entity, tree))
+            .collect();
+        trees.sort_by_key(|(_, tree)| tree.plot);
+        let selected = trees
+            .iter()
+            .position(|(entity, _)| *entity == self.current_tree.0)?;
+
+        Some(SaveGame {
+            saved_at: Some(now),
+            trees: trees.into_iter().map(|(_, tree)| tree.clone()).collect(),
+            selected,
+            current_quest_info: self.current_quest_info.clone(),
+            quest_pool: self.quest_pool.clone(),
+            memorial: self.memorial.clone(),
+            scheduler: self.scheduler.clone(),
+            history: self.history.clone(),
+            streak: self.streak.clone(),
+            achievements: self.achievements.clone(),
+            wallet: self.wallet.clone(),
+            wardrobe: self.wardrobe.clone(),
+            profile: self.profile.clone(),
+        })
+    }
+}
+
+fn write_save_game(
+    saved_state: SavedState,
+    mut last_written: Local<Option<DateTime<Utc>>>,
+    location: Res<SaveLocation>,
+    clock: Res<GameClock>,
+) {
+    let now = clock.now();
+    let heartbeat_due = last_written
+        .is_none_or(|last_written| now - last_written > Duration::seconds(HEARTBEAT_SECS));
+    if !heartbeat_due && !saved_state.is_changed() {
+        return;
+    }
+    let Some(save_game) = saved_state.to_save_game(now) else {
+        return;
+    };
+
+    *last_written = Some(now);
+
+    if let Err(err) = save_game.write(&location) {
+        error!("Failed to write save game: {}", err);
+    }
+}
@@ -0,0 +1,42 @@
+//! Where the save game lives: a file next to the game on native, `localStorage` on the web.
+
+#[cfg(not(target_arch = "wasm32"))]
+pub const DEFAULT_LOCATION: &str = "forestry_save.ron";
+
+#[cfg(target_arch = "wasm32")]
+pub const DEFAULT_LOCATION: &str = "forestry_save";
+
+#[cfg(not(target_arch = "wasm32"))]
+pub fn read(path: &str) -> Option<String> {
+    match std::fs::read_to_string(path) {
+        Ok(contents) => Some(contents),
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => None,
+        Err(err) => {
+            bevy::log::error!("Failed to read save game from {}: {}", path, err);
+            None
+        }
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+pub fn write(path: &str, contents: &str) -> Result<(), String> {
+    std::fs::write(path, contents).map_err(|err| err.to_string())
+}
+
+#[cfg(target_arch = "wasm32")]
+fn local_storage() -> Option<web_sys::Storage> {
+    web_sys::window()?.local_storage().ok()?
+}
+
+#[cfg(target_arch = "wasm32")]
+pub fn read(key: &str) -> Option<String> {
+    local_storage()?.get_item(key).ok()?
+}
+
+#[cfg(target_arch = "wasm32")]
+pub fn write(key: &str, contents: &str) -> Result<(), String> {
+    local_storage()
+        .ok_or_else(|| "localStorage is unavailable".to_string())?
+        .set_item(key, contents)
+        .map_err(|err| format!("{:?}", err))
+}
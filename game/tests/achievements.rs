@@ -0,0 +1,88 @@
+mod common;
+
+use bevy::ecs::event::{Events, ManualEventReader};
+use chrono::Duration;
+use common::{quests, start_time, Harness};
+use game::data::{
+    AchievementUnlockedEvent, Achievements, Growth, TreeInfo, TreeKind, GROWTH_STAGE_POINTS,
+};
+
+fn harness_with_achievements(quest_count: usize) -> Harness {
+    let mut harness = Harness::with_quests(quests(quest_count));
+    harness.update();
+    harness
+}
+
+fn unlocked(
+    harness: &Harness,
+    reader: &mut ManualEventReader<AchievementUnlockedEvent>,
+) -> Vec<&'static str> {
+    reader
+        .iter(harness.resource::<Events<AchievementUnlockedEvent>>())
+        .map(|event| event.id)
+        .collect()
+}
+
+#[test]
+fn the_first_completed_quest_unlocks_once() {
+    let mut harness = harness_with_achievements(2);
+    let mut reader = ManualEventReader::default();
+
+    harness.complete_quest();
+    assert_eq!(unlocked(&harness, &mut reader), vec!["first_quest"]);
+    let achievements = harness.resource::<Achievements>();
+    assert_eq!(
+        achievements.unlocked.get("first_quest"),
+        Some(&start_time())
+    );
+
+    harness.advance(Duration::seconds(10));
+    harness.complete_quest();
+    assert!(unlocked(&harness, &mut reader).is_empty());
+}
+
+#[test]
+fn nursing_a_tree_out_of_bad_health_unlocks() {
+    let mut harness = harness_with_achievements(1);
+    harness.set_vitality(10.0);
+    let mut reader = ManualEventReader::default();
+
+    harness.complete_quest();
+    assert!(unlocked(&harness, &mut reader).contains(&"survived_bad"));
+}
+
+#[test]
+fn growing_a_birch_to_full_size_unlocks() {
+    let mut harness = harness_with_achievements(1);
+    let almost_grown_birch = TreeInfo {
+        kind: TreeKind::Birch,
+        growth: Growth {
+            points: GROWTH_STAGE_POINTS[1] - 1,
+            stage: 1,
+            ..harness.tree().growth.clone()
+        },
+        ..harness.tree().clone()
+    };
+    *harness.tree_mut() = almost_grown_birch;
+    let mut reader = ManualEventReader::default();
+
+    harness.complete_quest();
+    assert!(unlocked(&harness, &mut reader).contains(&"grown_birch"));
+}
+
+#[test]
+fn ten_days_of_completed_quests_unlock_the_streak() {
+    let mut harness = harness_with_achievements(10);
+    let mut reader = ManualEventReader::default();
+
+    for day in 0..10 {
+        assert!(
+            !unlocked(&harness, &mut reader).contains(&"ten_day_streak"),
+            "day {}",
+            day
+        );
+        harness.complete_quest();
+        harness.advance(Duration::days(1));
+    }
+    assert!(unlocked(&harness, &mut reader).contains(&"ten_day_streak"));
+}
@@ -0,0 +1,94 @@
+mod common;
+
+use chrono::Duration;
+use common::{quest, Harness};
+use game::data::{Prerequisite, Quest, QuestPool};
+
+fn requiring(name: &str, prerequisite: &str, times: u32) -> Quest {
+    Quest {
+        requires: vec![Prerequisite {
+            quest: prerequisite.to_string(),
+            times,
+        }],
+        ..quest(name, Duration::seconds(30))
+    }
+}
+
+fn completions(harness: &Harness, id: &str) -> u32 {
+    let quest_pool = harness.app.world.resource::<QuestPool>();
+    quest_pool.completions.get(id).copied().unwrap_or_default()
+}
+
+#[test]
+fn locked_quests_wait_for_their_prerequisites() {
+    let mut harness = Harness::with_quests(vec![
+        requiring("Start composting", "Tidy up", 3),
+        quest("Tidy up", Duration::seconds(30)),
+        quest("Tidy up", Duration::seconds(30)),
+        quest("Tidy up", Duration::seconds(30)),
+    ]);
+    harness.update();
+
+    for completed in 1..=3 {
+        assert_eq!(harness.current_quest().unwrap().quest.name, "Tidy up");
+        harness.complete_quest();
+        assert_eq!(completions(&harness, "Tidy up"), completed);
+        harness.advance(Duration::seconds(10));
+    }
+    assert_eq!(
+        harness.current_quest().unwrap().quest.name,
+        "Start composting"
+    );
+}
+
+#[test]
+fn missed_quests_do_not_count_towards_unlocks() {
+    let mut harness = Harness::with_quests(vec![
+        requiring("Start composting", "Tidy up", 1),
+        quest("Tidy up", Duration::seconds(30)),
+    ]);
+    harness.update();
+
+    let events = harness.advance(Duration::seconds(31));
+    assert_eq!(events.missed, 1);
+    harness.advance(Duration::seconds(10));
+
+    assert_eq!(completions(&harness, "Tidy up"), 0);
+    assert!(harness.current_quest().is_none());
+    assert_eq!(harness.app.world.resource::<QuestPool>().queue.len(), 1);
+}
+
+#[test]
+fn completing_a_quest_queues_its_follow_ups() {
+    let mut harness = Harness::with_quests(vec![Quest {
+        follow_ups: vec![quest("Turn the compost", Duration::seconds(30))],
+        ..quest("Start composting", Duration::seconds(30))
+    }]);
+    harness.update();
+    assert!(harness.app.world.resource::<QuestPool>().queue.is_empty());
+
+    harness.complete_quest();
+    harness.advance(Duration::seconds(10));
+
+    assert_eq!(
+        harness.current_quest().unwrap().quest.name,
+        "Turn the compost"
+    );
+}
+
+#[test]
+fn quest_files_declare_chains() {
+    let mut harness = Harness::with_quest_files();
+    harness.wait_for_quest_pool();
+
+    let quest_pool = harness.app.world.resource::<QuestPool>();
+    let composting = quest_pool
+        .queue
+        .iter()
+        .find(|quest| quest.id == "Start composting")
+        .unwrap();
+    assert!(!quest_pool.is_unlocked(composting));
+    assert_eq!(composting.requires[0].quest, "Water the plants");
+    assert_eq!(composting.requires[0].times, 3);
+    assert_eq!(composting.follow_ups[0].id, "Turn the compost");
+}
@@ -0,0 +1,204 @@
+//! Headless harness driving `DataPlugin` frame by frame on a mock clock.
+
+#![allow(dead_code)]
+
+use bevy::app::App;
+use bevy::asset::AssetPlugin;
+use bevy::ecs::event::{Event, Events, ManualEventReader};
+use bevy::prelude::*;
+use chrono::{DateTime, Duration, FixedOffset, Utc};
+use game::clock::GameClock;
+use game::data::{
+    ActiveQuest, CurrentQuestInfo, CurrentTree, DataPlugin, Difficulty, Health, PlantTreeEvent,
+    Quest, QuestAppearedEvent, QuestCatalog, QuestCompletedEvent, QuestFiles, QuestMissedEvent,
+    QuestPool, QuestProgressEvent, QuestScheduler, ScheduleTimeZone, TreeInfo,
+};
+
+pub fn start_time() -> DateTime<Utc> {
+    "2023-03-01T12:00:00Z".parse().unwrap()
+}
+
+pub fn quest(name: &str, time_to_complete: Duration) -> Quest {
+    Quest {
+        id: name.to_string(),
+        name: name.to_string(),
+        description: format!("{} description", name),
+        time_to_complete,
+        ..Default::default()
+    }
+}
+
+/// A 30 second quest named after its `difficulty`.
+pub fn quest_of(difficulty: Difficulty) -> Quest {
+    Quest {
+        difficulty,
+        ..quest(&format!("{:?} quest", difficulty), Duration::seconds(30))
+    }
+}
+
+/// `count` numbered quests of 30 seconds each.
+pub fn quests(count: usize) -> Vec<Quest> {
+    (0..count)
+        .map(|index| quest(&format!("Quest {}", index), Duration::seconds(30)))
+        .collect()
+}
+
+/// Events emitted during a single frame.
+#[derive(Default, Debug, PartialEq, Eq)]
+pub struct FrameEvents {
+    pub appeared: usize,
+    pub completed: usize,
+    pub missed: usize,
+}
+
+pub struct Harness {
+    pub app: App,
+    appeared: ManualEventReader<QuestAppearedEvent>,
+    completed: ManualEventReader<QuestCompletedEvent>,
+    missed: ManualEventReader<QuestMissedEvent>,
+}
+
+impl Harness {
+    /// An app whose quest pool is loaded from the real `assets/quests` files.
+    pub fn with_quest_files() -> Self {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .add_plugin(AssetPlugin {
+                asset_folder: "../assets".to_string(),
+                ..default()
+            })
+            .insert_resource(GameClock::mock(start_time()))
+            .add_plugin(DataPlugin)
+            // One quest at a time keeps the quest flow easy to follow.
+            .insert_resource(QuestScheduler {
+                time_zone: ScheduleTimeZone::Fixed(FixedOffset::east(0)),
+                max_active: 1,
+                ..default()
+            });
+
+        Harness {
+            app,
+            appeared: ManualEventReader::default(),
+            completed: ManualEventReader::default(),
+            missed: ManualEventReader::default(),
+        }
+    }
+
+    /// An app whose quest pool holds exactly `quests`, bypassing the quest files.
+    pub fn with_quests(quests: Vec<Quest>) -> Self {
+        let mut harness = Harness::with_quest_files();
+        harness.app.insert_resource(QuestFiles(Vec::new()));
+        harness.app.insert_resource(QuestPool {
+            queue: quests.into(),
+            ..Default::default()
+        });
+        harness
+    }
+
+    /// Runs one frame and reports the quest events it emitted.
+    pub fn update(&mut self) -> FrameEvents {
+        self.app.update();
+        FrameEvents {
+            appeared: read_events(&self.app, &mut self.appeared),
+            completed: read_events(&self.app, &mut self.completed),
+            missed: read_events(&self.app, &mut self.missed),
+        }
+    }
+
+    /// Moves the mock clock forward and runs one frame.
+    pub fn advance(&mut self, duration: Duration) -> FrameEvents {
+        self.app.world.resource_mut::<GameClock>().advance(duration);
+        self.update()
+    }
+
+    /// Makes all the progress the first active quest still needs and runs one frame.
+    pub fn complete_quest(&mut self) -> FrameEvents {
+        let remaining = self.current_quest().map(|active_quest| {
+            let steps = active_quest.progress..active_quest.quest.target();
+            (active_quest.quest.id.clone(), steps)
+        });
+        if let Some((quest_id, steps)) = remaining {
+            for _ in steps {
+                self.app.world.send_event(QuestProgressEvent {
+                    quest_id: quest_id.clone(),
+                });
+            }
+        }
+        self.update()
+    }
+
+    /// Presses the "done" button once, making one step of progress, and runs one frame.
+    pub fn progress_quest(&mut self) -> FrameEvents {
+        let quest_id = self.current_quest_id();
+        self.send(QuestProgressEvent { quest_id })
+    }
+
+    /// Sends `event`, like a press of one of the quest panel buttons, and runs one frame.
+    pub fn send<E: Event>(&mut self, event: E) -> FrameEvents {
+        self.app.world.send_event(event);
+        self.update()
+    }
+
+    pub fn plant_tree(&mut self) {
+        self.app.world.send_event(PlantTreeEvent);
+        self.update();
+    }
+
+    /// Runs frames until the quest files have been loaded into the pool.
+    pub fn wait_for_quest_pool(&mut self) {
+        for _ in 0..1000 {
+            self.update();
+            if self.app.world.resource::<QuestCatalog>().loaded {
+                return;
+            }
+            std::thread::sleep(std::time::Duration::from_millis(5));
+        }
+        panic!("quest files did not load");
+    }
+
+    /// The first of the active quests.
+    pub fn current_quest(&self) -> Option<&ActiveQuest> {
+        self.active_quests().first()
+    }
+
+    /// Id of the first active quest, or an empty id that matches no quest.
+    pub fn current_quest_id(&self) -> String {
+        self.current_quest()
+            .map(|active_quest| active_quest.quest.id.clone())
+            .unwrap_or_default()
+    }
+
+    pub fn active_quests(&self) -> &[ActiveQuest] {
+        &self.app.world.resource::<CurrentQuestInfo>().active_quests
+    }
+
+    pub fn tree(&self) -> &TreeInfo {
+        let current_tree = self.app.world.resource::<CurrentTree>().0;
+        self.app.world.get::<TreeInfo>(current_tree).unwrap()
+    }
+
+    pub fn tree_mut(&mut self) -> Mut<'_, TreeInfo> {
+        let current_tree = self.app.world.resource::<CurrentTree>().0;
+        self.app.world.get_mut::<TreeInfo>(current_tree).unwrap()
+    }
+
+    pub fn set_vitality(&mut self, vitality: f32) {
+        self.tree_mut().vitality = vitality;
+    }
+
+    pub fn resource<R: Resource>(&self) -> &R {
+        self.app.world.resource::<R>()
+    }
+
+    pub fn resource_mut<R: Resource>(&mut self) -> Mut<'_, R> {
+        self.app.world.resource_mut::<R>()
+    }
+
+    pub fn health(&self) -> Health {
+        self.tree().health()
+    }
+}
+
+fn read_events<T: Event>(app: &App, reader: &mut ManualEventReader<T>) -> usize {
+    reader.iter(app.world.resource::<Events<T>>()).count()
+}
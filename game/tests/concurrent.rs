@@ -0,0 +1,117 @@
+mod common;
+
+use chrono::Duration;
+use common::{quest, start_time, Harness};
+use game::data::{
+    catch_up, ActiveQuest, CurrentQuestInfo, DeathRules, Quest, QuestPool, QuestProgressEvent,
+    QuestScheduler, QuestSelector, TreeInfo,
+};
+
+fn with_max_active(max_active: usize, quests: Vec<Quest>) -> Harness {
+    let mut harness = Harness::with_quests(quests);
+    harness
+        .app
+        .world
+        .resource_mut::<QuestScheduler>()
+        .max_active = max_active;
+    harness
+}
+
+fn active_names(harness: &Harness) -> Vec<&str> {
+    harness
+        .active_quests()
+        .iter()
+        .map(|active_quest| active_quest.quest.name.as_str())
+        .collect()
+}
+
+#[test]
+fn quests_appear_a_cooldown_apart_up_to_the_limit() {
+    let mut harness = with_max_active(
+        2,
+        vec![
+            quest("Water", Duration::minutes(10)),
+            quest("Prune", Duration::minutes(10)),
+            quest("Mulch", Duration::minutes(10)),
+        ],
+    );
+
+    assert_eq!(harness.update().appeared, 1);
+    assert_eq!(harness.advance(Duration::seconds(2)).appeared, 0);
+    assert_eq!(harness.advance(Duration::seconds(4)).appeared, 1);
+    assert_eq!(harness.advance(Duration::minutes(1)).appeared, 0);
+    assert_eq!(active_names(&harness), vec!["Water", "Prune"]);
+
+    let events = harness.send(QuestProgressEvent {
+        quest_id: "Prune".to_string(),
+    });
+    assert_eq!(events.completed, 1);
+    assert_eq!(active_names(&harness), vec!["Water"]);
+
+    harness.advance(Duration::seconds(6));
+    assert_eq!(active_names(&harness), vec!["Water", "Mulch"]);
+}
+
+#[test]
+fn each_active_quest_keeps_its_own_deadline() {
+    let mut harness = with_max_active(
+        2,
+        vec![
+            quest("Water", Duration::minutes(1)),
+            quest("Prune", Duration::minutes(10)),
+        ],
+    );
+    harness.update();
+    harness.advance(Duration::seconds(6));
+
+    let events = harness.advance(Duration::seconds(55));
+    assert_eq!(events.missed, 1);
+    assert_eq!(active_names(&harness), vec!["Prune"]);
+}
+
+#[test]
+fn catch_up_replays_concurrent_quests() {
+    let mut current_quest_info = CurrentQuestInfo {
+        active_quests: vec![ActiveQuest::new(
+            quest("Sweep", Duration::seconds(30)),
+            start_time(),
+        )],
+        ..Default::default()
+    };
+    let mut quest_pool = QuestPool {
+        queue: vec![
+            quest("Water", Duration::seconds(60)),
+            quest("Mulch", Duration::hours(10)),
+        ]
+        .into(),
+        ..Default::default()
+    };
+    let mut scheduler = QuestScheduler {
+        max_active: 2,
+        ..Default::default()
+    };
+
+    let missed = catch_up(
+        start_time() + Duration::hours(1),
+        &mut current_quest_info,
+        &mut quest_pool,
+        &mut TreeInfo::default(),
+        &DeathRules::default(),
+        &mut scheduler,
+        &mut QuestSelector::default(),
+    );
+
+    // Water appears 5s after Sweep, so both are missed; Mulch appears 5s after Sweep's deadline.
+    assert_eq!(
+        missed
+            .iter()
+            .map(|record| record.name.as_str())
+            .collect::<Vec<_>>(),
+        vec!["Sweep", "Water"]
+    );
+    assert_eq!(current_quest_info.active_quests.len(), 1);
+    assert_eq!(
+        current_quest_info.active_quests[0].appeared_at,
+        start_time() + Duration::seconds(35)
+    );
+}
@@ -0,0 +1,184 @@
+mod common;
+
+use chrono::Duration;
+use common::{quest, quests, start_time, Harness};
+use game::data::{
+    catch_up, ActiveQuest, CurrentQuestInfo, DeathRules, Health, Memorial, QuestHistory, QuestPool,
+    QuestScheduler, QuestSelector, QuestSkippedEvent, ReplantTreeEvent, Streak, TreeInfo, TreeItem,
+};
+
+/// Misses every quest in turn until the selected tree dies.
+fn neglect_until_dead(harness: &mut Harness) {
+    for _ in 0..10 {
+        if harness.tree().is_dead() {
+            return;
+        }
+        harness.advance(Duration::seconds(31));
+        harness.advance(Duration::seconds(6));
+    }
+    panic!("the tree did not die");
+}
+
+#[test]
+fn a_tree_dies_after_repeated_misses_at_bad_health() {
+    let mut harness = Harness::with_quests(quests(10));
+    harness.app.insert_resource(DeathRules { misses_at_bad: 2 });
+    harness.update();
+
+    neglect_until_dead(&mut harness);
+
+    // Good -> Moderate -> Bad, then two more misses at Bad.
+    let tree = harness.tree();
+    assert_eq!(tree.health(), Health::Bad);
+    assert_eq!(tree.misses_at_bad, 2);
+    assert_eq!(
+        tree.died_at,
+        Some(start_time() + Duration::seconds(31 + 37 * 3))
+    );
+
+    // A dead tree gets no more quests.
+    harness.advance(Duration::minutes(5));
+    assert!(harness.current_quest().is_none());
+}
+
+#[test]
+fn completing_a_quest_resets_the_misses_at_bad() {
+    let mut tree = TreeInfo {
+        vitality: 0.0,
+        misses_at_bad: 1,
+        ..Default::default()
+    };
+    let death_rules = DeathRules { misses_at_bad: 2 };
+
+    tree.complete_quest(start_time(), 10.0, 1);
+    assert_eq!(tree.health(), Health::Bad);
+    assert!(!tree.miss_quest(start_time(), 10.0, &death_rules));
+    assert!(!tree.is_dead());
+}
+
+#[test]
+fn a_replanted_tree_skips_names_held_by_other_trees() {
+    let mut harness = Harness::with_quests(quests(10));
+    harness.app.insert_resource(DeathRules { misses_at_bad: 1 });
+    harness.update();
+    neglect_until_dead(&mut harness);
+    assert_eq!(harness.tree().name, "John");
+    harness.app.world.spawn(TreeItem {
+        info: TreeInfo {
+            name: "Hazel".to_string(),
+            plot: 1,
+            ..Default::default()
+        },
+    });
+
+    harness.app.world.send_event(ReplantTreeEvent);
+    harness.update();
+
+    assert_eq!(harness.tree().name, "Rowan");
+}
+
+#[test]
+fn replanting_memorializes_the_dead_tree_and_resumes_quests() {
+    let mut harness = Harness::with_quests(quests(10));
+    harness.app.insert_resource(DeathRules { misses_at_bad: 1 });
+    harness.update();
+    neglect_until_dead(&mut harness);
+    let dead_tree = harness.tree().clone();
+
+    harness.app.world.send_event(ReplantTreeEvent);
+    harness.update();
+
+    let memorial = harness.app.world.resource::<Memorial>();
+    assert_eq!(memorial.entries.len(), 1);
+    assert_eq!(memorial.entries[0].name, dead_tree.name);
+    let tree = harness.tree();
+    assert!(!tree.is_dead());
+    assert_ne!(tree.name, dead_tree.name);
+    assert_ne!(tree.seed, dead_tree.seed);
+    assert_eq!(tree.plot, dead_tree.plot);
+    assert_eq!(tree.growth.stage, 0);
+
+    harness.advance(Duration::seconds(6));
+    assert!(harness.current_quest().is_some());
+}
+
+#[test]
+fn catch_up_stops_the_quest_flow_when_the_tree_dies() {
+    let mut current_quest_info = CurrentQuestInfo {
+        active_quests: vec![ActiveQuest::new(
+            quest("Sweep", Duration::seconds(30)),
+            start_time(),
+        )],
+        ..Default::default()
+    };
+    let mut quest_pool = QuestPool {
+        queue: quests(5).into(),
+        ..Default::default()
+    };
+    let mut tree = TreeInfo {
+        vitality: 0.0,
+        ..Default::default()
+    };
+
+    let missed = catch_up(
+        start_time() + Duration::hours(1),
+        &mut current_quest_info,
+        &mut quest_pool,
+        &mut tree,
+        &DeathRules { misses_at_bad: 2 },
+        &mut QuestScheduler {
+            max_active: 1,
+            ..Default::default()
+        },
+        &mut QuestSelector::default(),
+    );
+
+    assert_eq!(
+        missed
+            .iter()
+            .map(|record| record.name.as_str())
+            .collect::<Vec<_>>(),
+        vec!["Sweep", "Quest 0"]
+    );
+    assert_eq!(tree.died_at, Some(start_time() + Duration::seconds(65)));
+    assert!(current_quest_info.active_quests.is_empty());
+    assert_eq!(quest_pool.queue.len(), 4);
+}
+
+#[test]
+fn quests_expiring_on_a_dead_tree_wait_for_the_replanted_one() {
+    let mut harness = Harness::with_quests(vec![
+        quest("Water", Duration::seconds(30)),
+        quest("Prune", Duration::seconds(30)),
+    ]);
+    harness.app.insert_resource(Streak {
+        current: 4,
+        best: 4,
+        freezes: 0,
+    });
+    harness.update();
+    harness.tree_mut().died_at = Some(start_time());
+
+    assert_eq!(harness.advance(Duration::seconds(31)).missed, 0);
+    assert_eq!(harness.resource::<Streak>().current, 4);
+    assert!(harness.resource::<QuestHistory>().records.is_empty());
+    assert!(harness.current_quest().is_none());
+
+    harness.send(ReplantTreeEvent);
+    harness.advance(Duration::seconds(6));
+    assert_eq!(harness.current_quest_id(), "Water");
+}
+
+#[test]
+fn quests_skipped_on_a_dead_tree_wait_for_the_replanted_one() {
+    let mut harness = Harness::with_quests(quests(2));
+    harness.update();
+    harness.tree_mut().died_at = Some(start_time());
+
+    harness.send(QuestSkippedEvent {
+        quest_id: "Quest 0".to_string(),
+    });
+    assert!(harness.resource::<QuestHistory>().records.is_empty());
+    let queue = &harness.resource::<QuestPool>().queue;
+    assert_eq!(queue.front().unwrap().id, "Quest 0");
+}
@@ -0,0 +1,85 @@
+mod common;
+
+use chrono::Duration;
+use common::{quests, start_time, Harness};
+use game::data::{CurrentTree, ReplantTreeEvent, TreeInfo, TreeKind, GROWTH_STAGE_POINTS};
+
+fn tree_count(harness: &mut Harness) -> usize {
+    harness
+        .app
+        .world
+        .query::<&TreeInfo>()
+        .iter(&harness.app.world)
+        .len()
+}
+
+fn grow_current_tree(harness: &mut Harness) {
+    while !harness.tree().growth.is_fully_grown() {
+        harness.complete_quest();
+        harness.advance(Duration::seconds(6));
+    }
+}
+
+#[test]
+fn planting_waits_until_the_forest_is_fully_grown() {
+    let mut harness = Harness::with_quests(quests(GROWTH_STAGE_POINTS[1] as usize + 1));
+    harness.update();
+
+    harness.plant_tree();
+    assert_eq!(tree_count(&mut harness), 1);
+
+    grow_current_tree(&mut harness);
+    harness.plant_tree();
+    assert_eq!(tree_count(&mut harness), 2);
+
+    harness.plant_tree();
+    assert_eq!(tree_count(&mut harness), 2);
+}
+
+#[test]
+fn quest_results_apply_to_the_newly_planted_tree() {
+    let mut harness = Harness::with_quests(quests(GROWTH_STAGE_POINTS[1] as usize + 2));
+    harness.update();
+    let first_tree = harness.app.world.resource::<CurrentTree>().0;
+    grow_current_tree(&mut harness);
+
+    harness.plant_tree();
+
+    let planted = harness.tree().clone();
+    assert_ne!(harness.app.world.resource::<CurrentTree>().0, first_tree);
+    assert_eq!(planted.plot, 1);
+    assert_eq!(planted.kind, TreeKind::Birch);
+    assert_eq!(planted.growth.stage, 0);
+
+    harness.complete_quest();
+    assert_eq!(harness.tree().growth.points, 1);
+    let first_tree_points = harness
+        .app
+        .world
+        .get::<TreeInfo>(first_tree)
+        .unwrap()
+        .growth
+        .points;
+    assert_eq!(first_tree_points, GROWTH_STAGE_POINTS[1]);
+}
+
+#[test]
+fn planted_trees_dont_take_names_given_out_on_replanting() {
+    let mut harness = Harness::with_quests(quests(GROWTH_STAGE_POINTS[1] as usize + 1));
+    harness.update();
+    harness.tree_mut().died_at = Some(start_time());
+    harness.send(ReplantTreeEvent);
+    grow_current_tree(&mut harness);
+
+    harness.plant_tree();
+
+    let names: Vec<String> = harness
+        .app
+        .world
+        .query::<&TreeInfo>()
+        .iter(&harness.app.world)
+        .map(|tree| tree.name.clone())
+        .collect();
+    assert_eq!(names.len(), 2);
+    assert_ne!(names[0], names[1]);
+}
@@ -0,0 +1,59 @@
+mod common;
+
+use chrono::Duration;
+use common::{quests, start_time, Harness};
+use game::data::{Growth, GROWTH_STAGE_POINTS, NEGLECT_STUNT_STREAK};
+
+#[test]
+fn a_new_tree_is_a_sapling_planted_at_startup() {
+    let mut harness = Harness::with_quests(Vec::new());
+    harness.update();
+
+    let growth = &harness.tree().growth;
+    assert_eq!(growth.stage, 0);
+    assert_eq!(growth.planted_at, Some(start_time()));
+    assert_eq!(
+        growth.age(start_time() + Duration::days(2)),
+        Duration::days(2)
+    );
+}
+
+#[test]
+fn completed_quests_grow_the_tree_through_its_stages() {
+    let mut harness = Harness::with_quests(quests(GROWTH_STAGE_POINTS[1] as usize));
+    harness.update();
+
+    let mut stages = Vec::new();
+    for _ in 0..GROWTH_STAGE_POINTS[1] {
+        harness.complete_quest();
+        stages.push(harness.tree().growth.stage);
+        harness.advance(Duration::seconds(6));
+    }
+
+    // The fifth completion in a row earns a streak bonus point.
+    assert_eq!(stages, vec![0, 0, 1, 1, 1, 1, 2, 2]);
+    assert_eq!(harness.tree().growth.points, GROWTH_STAGE_POINTS[1] + 1);
+}
+
+#[test]
+fn sustained_neglect_stunts_growth_without_shrinking_the_tree() {
+    let mut growth = Growth::default();
+    for _ in 0..GROWTH_STAGE_POINTS[0] + 1 {
+        growth.grow(1);
+    }
+    assert_eq!(growth.stage, 1);
+
+    for _ in 0..NEGLECT_STUNT_STREAK - 1 {
+        growth.neglect();
+    }
+    assert_eq!(growth.points, GROWTH_STAGE_POINTS[0] + 1);
+
+    for _ in 0..3 {
+        growth.neglect();
+    }
+    assert_eq!(growth.points, GROWTH_STAGE_POINTS[0]);
+    assert_eq!(growth.stage, 1);
+
+    assert!(!growth.grow(1));
+    assert_eq!(growth.neglect, 0);
+}
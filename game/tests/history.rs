@@ -0,0 +1,105 @@
+mod common;
+
+use chrono::{DateTime, Duration, Utc};
+use common::{quest, start_time, Harness};
+use game::data::{
+    ActiveQuest, Quest, QuestCategory, QuestHistory, QuestOutcome, QuestRejectedEvent,
+    QuestSkippedEvent,
+};
+
+fn history(harness: &Harness) -> &QuestHistory {
+    harness.app.world.resource::<QuestHistory>()
+}
+
+#[test]
+fn finished_quests_are_recorded_with_their_outcome() {
+    let mut harness = Harness::with_quests(vec![
+        quest("Water", Duration::minutes(10)),
+        quest("Prune", Duration::minutes(1)),
+        quest("Sweep", Duration::minutes(10)),
+        quest("Mulch", Duration::minutes(10)),
+    ]);
+    harness.update();
+    harness.advance(Duration::minutes(2));
+    harness.complete_quest();
+
+    harness.advance(Duration::seconds(10));
+    harness.advance(Duration::minutes(2));
+
+    harness.advance(Duration::seconds(10));
+    harness.send(QuestSkippedEvent {
+        quest_id: harness.current_quest_id(),
+    });
+
+    harness.advance(Duration::seconds(10));
+    harness.send(QuestRejectedEvent {
+        quest_id: harness.current_quest_id(),
+    });
+
+    let history = history(&harness);
+    let outcomes: Vec<(&str, QuestOutcome)> = history
+        .records
+        .iter()
+        .map(|record| (record.quest_id.as_str(), record.outcome))
+        .collect();
+    assert_eq!(
+        outcomes,
+        vec![
+            ("Water", QuestOutcome::Completed),
+            ("Prune", QuestOutcome::Missed),
+            ("Sweep", QuestOutcome::Skipped),
+            ("Mulch", QuestOutcome::Rejected),
+        ]
+    );
+    assert_eq!(history.records[0].appeared_at, start_time());
+    assert_eq!(history.records[0].time_taken(), Duration::minutes(2));
+    // The rejected quest doesn't count.
+    assert_eq!(history.completion_rate(), Some(1.0 / 3.0));
+}
+
+fn record(
+    history: &mut QuestHistory,
+    category: QuestCategory,
+    finished_at: DateTime<Utc>,
+    outcome: QuestOutcome,
+) {
+    let active_quest = ActiveQuest::new(
+        Quest {
+            category,
+            ..quest("Quest", Duration::hours(1))
+        },
+        finished_at - Duration::minutes(30),
+    );
+    history.record(&active_quest, finished_at, outcome);
+}
+
+#[test]
+fn stats_sum_up_the_history() {
+    let now = start_time();
+    let mut history = QuestHistory::default();
+    assert_eq!(history.completion_rate(), None);
+    assert_eq!(history.average_time_taken(), None);
+
+    let outcomes = [
+        (QuestCategory::Home, QuestOutcome::Completed),
+        (QuestCategory::Nature, QuestOutcome::Completed),
+        (QuestCategory::Nature, QuestOutcome::Completed),
+        (QuestCategory::Body, QuestOutcome::Missed),
+        (QuestCategory::Nature, QuestOutcome::Completed),
+        (QuestCategory::Mind, QuestOutcome::Rejected),
+        (QuestCategory::Home, QuestOutcome::Completed),
+    ];
+    for (index, (category, outcome)) in outcomes.into_iter().enumerate() {
+        let finished_at = now - Duration::weeks(3) + Duration::days(3 * index as i64);
+        record(&mut history, category, finished_at, outcome);
+    }
+
+    // The rejection doesn't count.
+    assert_eq!(history.completion_rate(), Some(5.0 / 6.0));
+    assert_eq!(
+        history.best_categories(),
+        vec![(QuestCategory::Nature, 3), (QuestCategory::Home, 2)]
+    );
+    assert_eq!(history.average_time_taken(), Some(Duration::minutes(30)));
+    assert_eq!(history.weekly_completions(now, 4), vec![1, 2, 1, 1]);
+}
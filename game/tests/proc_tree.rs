@@ -0,0 +1,19 @@
+use game::data::TreeKind;
+use game::home::{generate_tree_image, TreeKindTable};
+
+fn kind_table() -> TreeKindTable {
+    ron::from_str(include_str!("../../assets/trees/trees.kinds.ron")).unwrap()
+}
+
+fn pixels(table: &TreeKindTable, seed: u64, kind: TreeKind) -> Vec<u8> {
+    let params = &table.kinds[&kind];
+    let stage = params.stage(usize::MAX).unwrap();
+    generate_tree_image(params, seed, kind, stage.iterations, params.leaves.good).data
+}
+
+#[test]
+fn pixels_are_decided_by_seed_and_kind() {
+    let table = kind_table();
+    assert!(pixels(&table, 42, TreeKind::Oak) == pixels(&table, 42, TreeKind::Oak));
+    assert!(pixels(&table, 42, TreeKind::Oak) != pixels(&table, 43, TreeKind::Oak));
+}
@@ -0,0 +1,117 @@
+mod common;
+
+use bevy::ecs::event::{Events, ManualEventReader};
+use chrono::Duration;
+use common::{quest, quest_of, quests, Harness};
+use game::data::{
+    level_xp, unlock_level, Difficulty, LevelUpEvent, Profile, Quest, QuestCategory, QuestPool,
+    TreeKind, Unlock, XP_PER_QUEST, XP_PER_STAGE,
+};
+
+fn levels_reached(harness: &Harness, reader: &mut ManualEventReader<LevelUpEvent>) -> Vec<u32> {
+    reader
+        .iter(harness.resource::<Events<LevelUpEvent>>())
+        .map(|event| event.level)
+        .collect()
+}
+
+#[test]
+fn levels_take_more_xp_each_time() {
+    assert_eq!(level_xp(1), 0);
+    assert_eq!(level_xp(2), 100);
+    assert_eq!(level_xp(3), 300);
+
+    let profile = Profile { xp: 299 };
+    assert_eq!(profile.level(), 2);
+    assert_eq!(profile.level_progress(), (199, 200));
+    assert_eq!(Profile { xp: 300 }.level(), 3);
+}
+
+#[test]
+fn completed_quests_earn_xp_by_difficulty() {
+    for (difficulty, expected) in [
+        (Difficulty::Easy, 12),
+        (Difficulty::Medium, XP_PER_QUEST),
+        (Difficulty::Hard, 30),
+    ] {
+        let mut harness = Harness::with_quests(vec![quest_of(difficulty)]);
+        harness.update();
+
+        harness.complete_quest();
+        assert_eq!(
+            harness.resource::<Profile>().xp,
+            expected,
+            "{:?}",
+            difficulty
+        );
+    }
+
+    // Missed quests earn nothing.
+    let mut harness = Harness::with_quests(quests(1));
+    harness.update();
+    harness.advance(Duration::seconds(31));
+    assert_eq!(harness.resource::<Profile>().xp, 0);
+}
+
+#[test]
+fn growing_a_stage_earns_bonus_xp_and_levels_up() {
+    let mut harness = Harness::with_quests(quests(3));
+    harness.update();
+    let mut reader = ManualEventReader::default();
+
+    // The third point grows the sapling into its next stage.
+    for _ in 0..3 {
+        harness.complete_quest();
+        harness.advance(Duration::seconds(10));
+    }
+    assert_eq!(
+        harness.resource::<Profile>().xp,
+        3 * XP_PER_QUEST + XP_PER_STAGE
+    );
+    assert_eq!(levels_reached(&harness, &mut reader), vec![2]);
+    assert_eq!(harness.resource::<Profile>().level(), 2);
+}
+
+#[test]
+fn tree_kinds_unlock_with_levels() {
+    let mut profile = Profile::default();
+    assert_eq!(profile.tree_kinds(), vec![TreeKind::Oak, TreeKind::Birch]);
+    assert_eq!(profile.tree_kind_for(2), TreeKind::Oak);
+
+    profile.xp = level_xp(unlock_level(Unlock::TreeKind(TreeKind::Cherry)));
+    assert_eq!(
+        profile.tree_kinds(),
+        vec![
+            TreeKind::Oak,
+            TreeKind::Birch,
+            TreeKind::Pine,
+            TreeKind::Cherry
+        ]
+    );
+    assert_eq!(profile.tree_kind_for(2), TreeKind::Pine);
+}
+
+#[test]
+fn locked_quest_categories_are_held_back_until_unlocked() {
+    let creative_quest = Quest {
+        category: QuestCategory::Creative,
+        ..quest("Sketch", Duration::seconds(30))
+    };
+    let mut harness =
+        Harness::with_quests(vec![creative_quest, quest("Sweep", Duration::seconds(30))]);
+    harness.update();
+
+    assert_eq!(harness.current_quest_id(), "Sweep");
+    harness.complete_quest();
+    harness.advance(Duration::seconds(10));
+    assert!(harness.current_quest().is_none());
+    let quest_pool = harness.resource::<QuestPool>();
+    assert!(quest_pool.queue.is_empty());
+    assert_eq!(quest_pool.locked.len(), 1);
+
+    harness.resource_mut::<Profile>().xp =
+        level_xp(unlock_level(Unlock::QuestCategory(QuestCategory::Creative)));
+    harness.advance(Duration::seconds(10));
+    assert_eq!(harness.current_quest_id(), "Sketch");
+    assert!(harness.resource::<QuestPool>().locked.is_empty());
+}
@@ -0,0 +1,61 @@
+mod common;
+
+use chrono::Duration;
+use common::{quest, Harness};
+use game::data::{Quest, QuestValidationError};
+
+fn counted(name: &str, target: u32) -> Quest {
+    Quest {
+        target,
+        ..quest(name, Duration::minutes(10))
+    }
+}
+
+#[test]
+fn counted_quests_complete_once_the_target_is_reached() {
+    let mut harness = Harness::with_quests(vec![counted("Drink water", 3)]);
+    harness.update();
+    let points = harness.tree().growth.points;
+
+    for progress in 1..3 {
+        let events = harness.progress_quest();
+        assert_eq!(events.completed, 0);
+        assert_eq!(harness.current_quest().unwrap().progress, progress);
+    }
+    assert_eq!(harness.tree().growth.points, points);
+
+    let events = harness.progress_quest();
+    assert_eq!(events.completed, 1);
+    assert!(harness.current_quest().is_none());
+    assert!(harness.tree().growth.points > points);
+}
+
+#[test]
+fn steps_set_the_target() {
+    let stretch = Quest {
+        steps: vec!["Neck".to_string(), "Back".to_string()],
+        ..quest("Stretch", Duration::minutes(10))
+    };
+    assert_eq!(stretch.target(), 2);
+    assert!(matches!(
+        counted("Nothing", 0).validate(),
+        Err(QuestValidationError::ZeroTarget)
+    ));
+
+    let mut harness = Harness::with_quests(vec![stretch]);
+    harness.update();
+    assert_eq!(harness.progress_quest().completed, 0);
+    assert_eq!(harness.progress_quest().completed, 1);
+}
+
+#[test]
+fn partial_progress_does_not_save_a_missed_quest() {
+    let mut harness = Harness::with_quests(vec![counted("Walk", 3)]);
+    harness.update();
+    harness.progress_quest();
+    harness.progress_quest();
+
+    let events = harness.advance(Duration::minutes(11));
+    assert_eq!(events.missed, 1);
+    assert_eq!(events.completed, 0);
+}
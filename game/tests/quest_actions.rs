@@ -0,0 +1,93 @@
+mod common;
+
+use chrono::Duration;
+use common::{quest, Harness};
+use game::data::{
+    Quest, QuestActionRules, QuestPool, QuestRejectedEvent, QuestScheduler, QuestSkippedEvent,
+    QuestSnoozedEvent, Schedule, ScheduleDays,
+};
+
+fn quest_pool(harness: &Harness) -> &QuestPool {
+    harness.app.world.resource::<QuestPool>()
+}
+
+#[test]
+fn snoozed_quests_come_back_later_without_a_penalty() {
+    let mut harness = Harness::with_quests(vec![
+        quest("Water", Duration::hours(3)),
+        quest("Prune", Duration::hours(3)),
+    ]);
+    harness.update();
+    let vitality = harness.tree().vitality;
+
+    let events = harness.send(QuestSnoozedEvent {
+        quest_id: harness.current_quest_id(),
+    });
+    assert_eq!(events.missed, 0);
+    assert!(harness.current_quest().is_none());
+    assert_eq!(harness.tree().vitality, vitality);
+
+    harness.advance(Duration::seconds(10));
+    assert_eq!(harness.current_quest().unwrap().quest.name, "Prune");
+    harness.complete_quest();
+
+    let snooze_for = harness.app.world.resource::<QuestActionRules>().snooze_for;
+    harness.advance(snooze_for);
+    harness.advance(Duration::seconds(10));
+    assert_eq!(harness.current_quest().unwrap().quest.name, "Water");
+    assert!(quest_pool(&harness).snoozed.is_empty());
+}
+
+#[test]
+fn skipping_costs_less_than_missing_and_is_not_neglect() {
+    let mut harness = Harness::with_quests(vec![quest("Water", Duration::hours(3))]);
+    harness.update();
+    let vitality = harness.tree().vitality;
+    let penalty = harness.current_quest().unwrap().quest.scaled_penalty();
+
+    harness.send(QuestSkippedEvent {
+        quest_id: harness.current_quest_id(),
+    });
+
+    let lost = vitality - harness.tree().vitality;
+    assert!(lost > 0.0 && lost < penalty);
+    assert_eq!(harness.tree().growth.neglect, 0);
+    assert!(harness.current_quest().is_none());
+    assert!(quest_pool(&harness).queue.is_empty());
+}
+
+#[test]
+fn rejected_quests_leave_the_rotation() {
+    let mut harness = Harness::with_quests(vec![
+        quest("Water", Duration::hours(3)),
+        quest("Water", Duration::hours(3)),
+        quest("Prune", Duration::hours(3)),
+    ]);
+    harness
+        .app
+        .world
+        .resource_mut::<QuestScheduler>()
+        .recurring
+        .push(Quest {
+            schedule: Some(Schedule {
+                days: ScheduleDays::Daily,
+                at: "13:00:00".parse().unwrap(),
+                until: None,
+            }),
+            ..quest("Water", Duration::hours(3))
+        });
+    harness.update();
+
+    harness.send(QuestRejectedEvent {
+        quest_id: harness.current_quest_id(),
+    });
+    assert!(quest_pool(&harness).rejected.contains("Water"));
+
+    harness.advance(Duration::seconds(10));
+    assert_eq!(harness.current_quest().unwrap().quest.name, "Prune");
+    harness.complete_quest();
+
+    harness.advance(Duration::hours(2));
+    assert!(harness.current_quest().is_none());
+    assert!(quest_pool(&harness).queue.is_empty());
+}
@@ -0,0 +1,93 @@
+mod common;
+
+use bevy::ecs::event::{Event, Events, ManualEventReader};
+use chrono::Duration;
+use common::{quest, start_time, Harness};
+use game::data::{
+    CurrentTree, DeathRules, Difficulty, Quest, QuestAppearedEvent, QuestCompletedEvent,
+    QuestMissedEvent, GROWTH_STAGE_POINTS,
+};
+
+/// Events of type `E` sent since `reader` last looked.
+fn read<'a, E: Event>(harness: &'a Harness, reader: &'a mut ManualEventReader<E>) -> Vec<&'a E> {
+    reader
+        .iter(harness.app.world.resource::<Events<E>>())
+        .collect()
+}
+
+#[test]
+fn events_name_the_quest_tree_and_time() {
+    let mut harness = Harness::with_quests(vec![Quest {
+        difficulty: Difficulty::Hard,
+        ..quest("Water", Duration::minutes(10))
+    }]);
+    let mut appeared = ManualEventReader::<QuestAppearedEvent>::default();
+    let mut completed = ManualEventReader::<QuestCompletedEvent>::default();
+    harness.update();
+    let tree = harness.app.world.resource::<CurrentTree>().0;
+
+    let events = read(&harness, &mut appeared);
+    assert_eq!(events.len(), 1);
+    assert_eq!(events[0].quest_id, "Water");
+    assert_eq!(events[0].tree, tree);
+    assert_eq!(events[0].at, start_time());
+    assert_eq!(events[0].deadline, start_time() + Duration::minutes(10));
+
+    harness.set_vitality(40.0);
+    harness.advance(Duration::minutes(1));
+    harness.complete_quest();
+
+    let events = read(&harness, &mut completed);
+    assert_eq!(events.len(), 1);
+    assert_eq!(events[0].quest_id, "Water");
+    assert_eq!(events[0].tree, tree);
+    assert_eq!(events[0].at, start_time() + Duration::minutes(1));
+    assert!((events[0].vitality_gained - 35.0 * 1.5).abs() < 0.01);
+    assert_eq!(events[0].growth_points, 2);
+    assert_eq!(events[0].new_stage, None);
+}
+
+#[test]
+fn completed_events_report_a_new_stage() {
+    let quests = (0..GROWTH_STAGE_POINTS[0])
+        .map(|index| quest(&format!("Quest {}", index), Duration::minutes(10)))
+        .collect();
+    let mut harness = Harness::with_quests(quests);
+    let mut completed = ManualEventReader::<QuestCompletedEvent>::default();
+    harness.update();
+
+    let mut stages = Vec::new();
+    for _ in 0..GROWTH_STAGE_POINTS[0] {
+        harness.complete_quest();
+        stages.extend(
+            read(&harness, &mut completed)
+                .iter()
+                .map(|event| event.new_stage),
+        );
+        harness.advance(Duration::seconds(10));
+    }
+
+    let mut expected = vec![None; GROWTH_STAGE_POINTS[0] as usize - 1];
+    expected.push(Some(1));
+    assert_eq!(stages, expected);
+}
+
+#[test]
+fn missed_events_report_the_damage() {
+    let mut harness = Harness::with_quests(vec![quest("Water", Duration::minutes(10))]);
+    harness.app.insert_resource(DeathRules { misses_at_bad: 1 });
+    let mut missed = ManualEventReader::<QuestMissedEvent>::default();
+    harness.update();
+    let tree = harness.app.world.resource::<CurrentTree>().0;
+    harness.set_vitality(20.0);
+
+    harness.advance(Duration::minutes(11));
+
+    let events = read(&harness, &mut missed);
+    assert_eq!(events.len(), 1);
+    assert_eq!(events[0].quest_id, "Water");
+    assert_eq!(events[0].tree, tree);
+    // All the tree had left, less what decayed while the quest was open.
+    assert!((events[0].vitality_lost - 20.0).abs() < 0.1);
+    assert!(events[0].tree_died);
+}
@@ -0,0 +1,70 @@
+mod common;
+
+use chrono::Duration;
+use common::{quest, Harness};
+use game::data::{QuestCatalog, QuestFiles, QuestPool, QuestValidationError};
+
+/// Starts a game from `files`, given relative to the test fixtures folder.
+fn load_fixtures(files: &[&str]) -> Harness {
+    let mut harness = Harness::with_quest_files();
+    // Asset paths are relative to the `assets` folder next to this crate.
+    let files = files
+        .iter()
+        .map(|file| format!("../game/tests/fixtures/{}", file))
+        .collect();
+    harness.app.insert_resource(QuestFiles(files));
+    harness.wait_for_quest_pool();
+    harness
+}
+
+/// Ids of the active quests followed by the queued ones.
+fn offered_ids(harness: &Harness) -> Vec<String> {
+    let active = harness
+        .active_quests()
+        .iter()
+        .map(|active_quest| &active_quest.quest);
+    let queued = harness.app.world.resource::<QuestPool>().queue.iter();
+    active.chain(queued).map(|quest| quest.id.clone()).collect()
+}
+
+#[test]
+fn quests_without_a_name_or_time_are_invalid() {
+    let nameless = quest("", Duration::seconds(30));
+    assert!(matches!(
+        nameless.validate(),
+        Err(QuestValidationError::MissingName)
+    ));
+
+    let instant = quest("Blink", Duration::zero());
+    assert!(matches!(
+        instant.validate(),
+        Err(QuestValidationError::ZeroTimeToComplete)
+    ));
+}
+
+#[test]
+fn invalid_quests_are_skipped_while_the_rest_of_the_file_loads() {
+    let harness = load_fixtures(&["invalid.quests.ron"]);
+
+    let catalog = harness.app.world.resource::<QuestCatalog>();
+    assert_eq!(catalog.one_shot, vec!["Water the plants", "Stretch"]);
+    assert_eq!(catalog.quests.len(), 2);
+    assert_eq!(offered_ids(&harness), vec!["Water the plants", "Stretch"]);
+}
+
+#[test]
+fn quests_load_from_json_files() {
+    let harness = load_fixtures(&["stretches.quests.json"]);
+
+    let catalog = harness.app.world.resource::<QuestCatalog>();
+    assert_eq!(catalog.one_shot, vec!["Touch your toes", "Sit up straight"]);
+    let quest = &catalog.quests["Touch your toes"];
+    assert_eq!(quest.time_to_complete, Duration::seconds(45));
+    assert_eq!(quest.description, "Roots need stretching too");
+    // Follow-ups are looked up by id like any other quest.
+    assert!(catalog.quests.contains_key("Stand up straight"));
+    assert_eq!(
+        offered_ids(&harness),
+        vec!["Touch your toes", "Sit up straight"]
+    );
+}
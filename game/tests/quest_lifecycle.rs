@@ -0,0 +1,223 @@
+mod common;
+
+use bevy::prelude::{Res, ResMut, Resource};
+use chrono::Duration;
+use common::{quest, start_time, FrameEvents, Harness};
+use game::data::{
+    catch_up, ActiveQuest, CurrentQuestInfo, DeathRules, Health, QuestCatalog, QuestPool,
+    QuestScheduler, QuestSelector, TreeInfo,
+};
+
+#[test]
+fn default_quest_files_fill_the_pool() {
+    let mut harness = Harness::with_quest_files();
+    harness.wait_for_quest_pool();
+    harness.update();
+
+    assert_eq!(harness.current_quest().unwrap().quest.name, "Test");
+    assert_eq!(harness.app.world.resource::<QuestPool>().queue.len(), 3);
+}
+
+#[test]
+fn first_quest_appears_on_the_first_frame() {
+    let mut harness = Harness::with_quests(vec![quest("Water", Duration::seconds(30))]);
+
+    let events = harness.update();
+
+    assert_eq!(events.appeared, 1);
+    let active_quest = harness.current_quest().unwrap();
+    assert_eq!(active_quest.quest.name, "Water");
+    assert_eq!(active_quest.deadline, start_time() + Duration::seconds(30));
+}
+
+#[test]
+fn completing_a_quest_heals_the_tree_and_starts_the_cooldown() {
+    let mut harness = Harness::with_quests(vec![
+        quest("Water", Duration::seconds(30)),
+        quest("Prune", Duration::seconds(30)),
+        quest("Mulch", Duration::seconds(30)),
+    ]);
+    harness.update();
+    harness.advance(Duration::seconds(31));
+    assert_eq!(harness.health(), Health::Moderate);
+    harness.advance(Duration::seconds(6));
+    assert_eq!(harness.current_quest().unwrap().quest.name, "Prune");
+
+    let events = harness.complete_quest();
+
+    assert_eq!(events.completed, 1);
+    assert!(harness.current_quest().is_none());
+    assert_eq!(harness.health(), Health::Good);
+
+    // The completion must be applied exactly once, not on every frame the event is buffered.
+    harness.update();
+    harness.update();
+    assert_eq!(harness.health(), Health::Good);
+
+    assert_eq!(
+        harness.advance(Duration::seconds(4)),
+        FrameEvents::default()
+    );
+    assert!(harness.current_quest().is_none());
+
+    let events = harness.advance(Duration::seconds(2));
+    assert_eq!(events.appeared, 1);
+    assert_eq!(harness.current_quest().unwrap().quest.name, "Mulch");
+}
+
+#[test]
+fn missing_a_deadline_hurts_the_tree_once() {
+    let mut harness = Harness::with_quests(vec![
+        quest("Water", Duration::seconds(30)),
+        quest("Prune", Duration::seconds(10)),
+    ]);
+    harness.update();
+
+    assert_eq!(
+        harness.advance(Duration::seconds(30)),
+        FrameEvents::default()
+    );
+
+    let events = harness.advance(Duration::seconds(1));
+    assert_eq!(events.missed, 1);
+    assert!(harness.current_quest().is_none());
+    assert_eq!(harness.health(), Health::Moderate);
+
+    harness.update();
+    harness.update();
+    assert_eq!(harness.health(), Health::Moderate);
+
+    let events = harness.advance(Duration::seconds(6));
+    assert_eq!(events.appeared, 1);
+    harness.advance(Duration::seconds(11));
+    assert_eq!(harness.health(), Health::Bad);
+}
+
+#[test]
+fn catch_up_misses_every_expired_quest_in_order() {
+    let mut current_quest_info = CurrentQuestInfo::default();
+    let mut quest_pool = QuestPool {
+        queue: vec![
+            quest("Water", Duration::seconds(30)),
+            quest("Prune", Duration::seconds(30)),
+            quest("Mulch", Duration::hours(10)),
+        ]
+        .into(),
+        ..Default::default()
+    };
+    current_quest_info.active_quests.push(ActiveQuest::new(
+        quest("Sweep", Duration::seconds(30)),
+        start_time(),
+    ));
+    let mut tree = TreeInfo::default();
+
+    let missed = catch_up(
+        start_time() + Duration::hours(1),
+        &mut current_quest_info,
+        &mut quest_pool,
+        &mut tree,
+        &DeathRules::default(),
+        &mut QuestScheduler {
+            max_active: 1,
+            ..Default::default()
+        },
+        &mut QuestSelector::default(),
+    );
+
+    assert_eq!(
+        missed
+            .iter()
+            .map(|record| record.name.as_str())
+            .collect::<Vec<_>>(),
+        vec!["Sweep", "Water", "Prune"]
+    );
+    assert_eq!(tree.health(), Health::Bad);
+    assert_eq!(tree.growth.neglect, 3);
+    let active_quest = &current_quest_info.active_quests[0];
+    assert_eq!(active_quest.quest.name, "Mulch");
+    // Sweep expires at +30s, Water at +65s, Prune at +100s, Mulch appears 5s later.
+    assert_eq!(
+        active_quest.deadline,
+        start_time() + Duration::seconds(105) + Duration::hours(10)
+    );
+}
+
+#[derive(Resource, Default)]
+struct PoolChanges(usize);
+
+fn count_pool_changes(quest_pool: Res<QuestPool>, mut changes: ResMut<PoolChanges>) {
+    if quest_pool.is_changed() {
+        changes.0 += 1;
+    }
+}
+
+#[test]
+fn an_idle_pool_is_not_marked_as_changed() {
+    let mut harness = Harness::with_quests(vec![quest("Water", Duration::seconds(30))]);
+    harness
+        .app
+        .init_resource::<PoolChanges>()
+        .add_system_to_stage(bevy::app::CoreStage::Last, count_pool_changes);
+    harness.update();
+    harness.complete_quest();
+    harness.advance(Duration::seconds(10));
+    harness.app.world.resource_mut::<PoolChanges>().0 = 0;
+
+    // Nothing is left to draw, though a quest could appear.
+    for _ in 0..50 {
+        harness.advance(Duration::seconds(1));
+    }
+
+    assert!(harness.current_quest().is_none());
+    assert_eq!(harness.app.world.resource::<PoolChanges>().0, 0);
+}
+
+#[test]
+fn saved_quests_are_resolved_against_the_quest_files() {
+    let quest_pool = QuestPool {
+        queue: vec![
+            quest("Water", Duration::seconds(30)),
+            quest("Sweep", Duration::seconds(30)),
+        ]
+        .into(),
+        ..Default::default()
+    };
+    let saved = ron::to_string(&quest_pool).unwrap();
+    assert!(!saved.contains("Water description"));
+
+    let mut edited = quest("Water", Duration::minutes(5));
+    edited.description = "Water the tree twice".to_string();
+    let mut catalog = QuestCatalog::default();
+    catalog.quests.insert(edited.id.clone(), edited);
+
+    let mut quest_pool: QuestPool = ron::from_str(&saved).unwrap();
+    catalog.resolve(&mut CurrentQuestInfo::default(), &mut quest_pool);
+
+    // Edits to the quest files reach saved quests, and removed quests are dropped.
+    assert_eq!(quest_pool.queue.len(), 1);
+    assert_eq!(quest_pool.queue[0].description, "Water the tree twice");
+    assert_eq!(quest_pool.queue[0].time_to_complete, Duration::minutes(5));
+}
+
+#[test]
+fn restored_quests_keep_when_they_appeared() {
+    let current_quest_info = CurrentQuestInfo {
+        active_quests: vec![ActiveQuest::new(
+            quest("Water", Duration::seconds(30)),
+            start_time(),
+        )],
+        ..Default::default()
+    };
+    let saved = ron::to_string(&current_quest_info).unwrap();
+
+    let mut catalog = QuestCatalog::default();
+    catalog
+        .quests
+        .insert("Water".to_string(), quest("Water", Duration::minutes(5)));
+    let mut current_quest_info: CurrentQuestInfo = ron::from_str(&saved).unwrap();
+    catalog.resolve(&mut current_quest_info, &mut QuestPool::default());
+
+    let active_quest = &current_quest_info.active_quests[0];
+    assert_eq!(active_quest.appeared_at, start_time());
+    assert_eq!(active_quest.deadline, start_time() + Duration::seconds(30));
+}
@@ -0,0 +1,155 @@
+mod common;
+
+use chrono::Duration;
+use common::{quest, start_time, Harness};
+use game::data::{
+    ActiveQuest, AwaySummary, CurrentQuestInfo, QuestHistory, QuestOutcome, QuestPool, Streak,
+    TreeInfo, TreeKind, Wallet,
+};
+use game::save::{SaveGame, SaveLocation, SavePlugin};
+
+/// One-shot quests of `assets/quests/default.quests.ron`.
+const ONE_SHOT_QUESTS: [&str; 6] = [
+    "Test",
+    "Cheer up",
+    "Tidy up",
+    "Start composting",
+    "Sketch a leaf",
+    "Walk somewhere new",
+];
+
+/// A save location of its own for every test, as they run in parallel.
+fn save_location(test: &str) -> SaveLocation {
+    let path =
+        std::env::temp_dir().join(format!("forestry_save_{}_{}.ron", test, std::process::id()));
+    SaveLocation(path.display().to_string())
+}
+
+fn saved_game(active_quests: Vec<ActiveQuest>, queue: Vec<&str>) -> SaveGame {
+    SaveGame {
+        saved_at: Some(start_time()),
+        trees: vec![
+            TreeInfo {
+                name: "John".to_string(),
+                ..Default::default()
+            },
+            TreeInfo {
+                name: "Hazel".to_string(),
+                kind: TreeKind::Birch,
+                plot: 1,
+                ..Default::default()
+            },
+        ],
+        selected: 1,
+        current_quest_info: CurrentQuestInfo {
+            active_quests,
+            last_quest_finished: start_time(),
+        },
+        quest_pool: QuestPool {
+            queue: queue
+                .into_iter()
+                .map(|id| quest(id, Duration::seconds(30)))
+                .collect(),
+            introduced: ONE_SHOT_QUESTS.map(str::to_string).into(),
+            ..Default::default()
+        },
+        streak: Streak {
+            current: 3,
+            best: 5,
+            freezes: 0,
+        },
+        wallet: Wallet { leaves: 40 },
+        ..Default::default()
+    }
+}
+
+/// Writes `save_game` and starts a game from it, once the quest files have loaded.
+fn resume(test: &str, save_game: SaveGame) -> Harness {
+    let location = save_location(test);
+    save_game.write(&location).unwrap();
+
+    let mut harness = Harness::with_quest_files();
+    harness
+        .app
+        .insert_resource(location.clone())
+        .add_plugin(SavePlugin);
+    harness.wait_for_quest_pool();
+    std::fs::remove_file(&location.0).unwrap();
+    harness
+}
+
+fn queued_ids(harness: &Harness) -> Vec<String> {
+    harness
+        .app
+        .world
+        .resource::<QuestPool>()
+        .queue
+        .iter()
+        .map(|quest| quest.id.clone())
+        .collect()
+}
+
+#[test]
+fn a_saved_game_is_resumed_against_the_quest_files() {
+    let active_quest = ActiveQuest::new(quest("Cheer up", Duration::hours(1)), start_time());
+    let mut harness = resume("resumed", saved_game(vec![active_quest], vec!["Tidy up"]));
+
+    let mut names: Vec<String> = harness
+        .app
+        .world
+        .query::<&TreeInfo>()
+        .iter(&harness.app.world)
+        .map(|tree| tree.name.clone())
+        .collect();
+    names.sort();
+    assert_eq!(names, vec!["Hazel", "John"]);
+    assert_eq!(harness.tree().name, "Hazel");
+
+    // Saved quests take their definitions from the quest files.
+    let active_quest = harness.current_quest().unwrap();
+    assert_eq!(active_quest.quest.id, "Cheer up");
+    assert_eq!(
+        active_quest.quest.description,
+        "No ecological revolution can be done in a bad mood"
+    );
+    assert_eq!(active_quest.appeared_at, start_time());
+    assert_eq!(queued_ids(&harness), vec!["Tidy up"]);
+
+    assert_eq!(harness.app.world.resource::<Streak>().current, 3);
+    assert_eq!(harness.app.world.resource::<Wallet>().leaves, 40);
+    assert!(harness.app.world.get_resource::<AwaySummary>().is_none());
+}
+
+#[test]
+fn unknown_quest_ids_are_dropped_on_resume() {
+    let active_quest = ActiveQuest::new(quest("Long gone", Duration::hours(1)), start_time());
+    let harness = resume(
+        "unknown_ids",
+        saved_game(vec![active_quest], vec!["Also gone", "Tidy up"]),
+    );
+
+    assert!(harness.active_quests().is_empty());
+    assert_eq!(queued_ids(&harness), vec!["Tidy up"]);
+}
+
+#[test]
+fn time_away_is_caught_up_on_resume() {
+    let away_for = Duration::hours(2);
+    let active_quest = ActiveQuest::new(
+        quest("Cheer up", Duration::hours(1)),
+        start_time() - away_for,
+    );
+    let mut save_game = saved_game(vec![active_quest], Vec::new());
+    save_game.saved_at = Some(start_time() - away_for);
+    save_game.current_quest_info.last_quest_finished = start_time() - away_for;
+
+    let harness = resume("caught_up", save_game);
+
+    let summary = harness.app.world.resource::<AwaySummary>();
+    assert_eq!(summary.away_for, away_for);
+    assert_eq!(summary.missed_quests, vec!["Cheer up"]);
+    assert_eq!(harness.app.world.resource::<Streak>().current, 0);
+    let history = harness.app.world.resource::<QuestHistory>();
+    assert_eq!(history.records.len(), 1);
+    assert_eq!(history.records[0].outcome, QuestOutcome::Missed);
+}
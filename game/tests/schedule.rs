@@ -0,0 +1,309 @@
+mod common;
+
+use chrono::{
+    DateTime, Duration, FixedOffset, LocalResult, NaiveDate, NaiveDateTime, NaiveTime, TimeZone,
+    Utc, Weekday,
+};
+use common::{quest, start_time, Harness};
+use game::data::{
+    catch_up, ActiveQuest, CurrentQuestInfo, DeathRules, Quest, QuestPool, QuestScheduler,
+    QuestSelector, Schedule, ScheduleDays, ScheduleTimeZone, TreeInfo,
+};
+
+fn at(time: &str) -> DateTime<Utc> {
+    time.parse().unwrap()
+}
+
+fn schedule(days: ScheduleDays, at: &str, until: Option<&str>) -> Schedule {
+    Schedule {
+        days,
+        at: at.parse().unwrap(),
+        until: until.map(|until| until.parse::<NaiveTime>().unwrap()),
+    }
+}
+
+fn scheduled(name: &str, schedule: Schedule) -> Quest {
+    Quest {
+        schedule: Some(schedule),
+        ..quest(name, Duration::hours(1))
+    }
+}
+
+#[test]
+fn occurrences_follow_days_and_time_windows() {
+    let utc = FixedOffset::east(0);
+    let daily = schedule(ScheduleDays::Daily, "08:00:00", Some("10:00:00"));
+    // 2023-03-01 is a Wednesday.
+    assert_eq!(daily.open_occurrence(at("2023-03-01T07:59:00Z"), utc), None);
+    assert_eq!(
+        daily.open_occurrence(at("2023-03-01T09:30:00Z"), utc),
+        Some(at("2023-03-01T08:00:00Z"))
+    );
+    assert_eq!(daily.open_occurrence(at("2023-03-01T10:01:00Z"), utc), None);
+
+    let weekdays = schedule(ScheduleDays::Weekdays, "08:00:00", None);
+    assert_eq!(
+        weekdays.open_occurrence(at("2023-03-05T12:00:00Z"), utc),
+        Some(at("2023-03-03T08:00:00Z"))
+    );
+
+    let weekly = schedule(ScheduleDays::Weekly(Weekday::Sun), "10:00:00", None);
+    assert_eq!(
+        weekly.open_occurrence(at("2023-03-01T12:00:00Z"), utc),
+        Some(at("2023-02-26T10:00:00Z"))
+    );
+
+    // Schedules are written in local time.
+    let utc_plus_two = FixedOffset::east(2 * 3600);
+    assert_eq!(
+        daily.open_occurrence(at("2023-03-01T06:30:00Z"), utc_plus_two),
+        Some(at("2023-03-01T06:00:00Z"))
+    );
+}
+
+#[test]
+fn overnight_windows_run_past_midnight() {
+    let utc = FixedOffset::east(0);
+    let nightly = schedule(ScheduleDays::Daily, "22:00:00", Some("02:00:00"));
+    assert_eq!(
+        nightly.open_occurrence(at("2023-03-01T21:59:00Z"), utc),
+        None
+    );
+    assert_eq!(
+        nightly.open_occurrence(at("2023-03-01T23:00:00Z"), utc),
+        Some(at("2023-03-01T22:00:00Z"))
+    );
+    assert_eq!(
+        nightly.open_occurrence(at("2023-03-02T01:30:00Z"), utc),
+        Some(at("2023-03-01T22:00:00Z"))
+    );
+    assert_eq!(
+        nightly.open_occurrence(at("2023-03-02T02:01:00Z"), utc),
+        None
+    );
+
+    // The window belongs to the day it starts on.
+    let friday_night = schedule(
+        ScheduleDays::Weekly(Weekday::Fri),
+        "22:00:00",
+        Some("02:00:00"),
+    );
+    assert_eq!(
+        friday_night.open_occurrence(at("2023-03-04T01:00:00Z"), utc),
+        Some(at("2023-03-03T22:00:00Z"))
+    );
+    assert_eq!(
+        friday_night.open_occurrence(at("2023-03-04T23:00:00Z"), utc),
+        None
+    );
+}
+
+/// UTC that switches to summer time at 01:00 on 2023-03-26, like London.
+#[derive(Clone, Copy)]
+struct SummerTime;
+
+impl SummerTime {
+    fn switch() -> NaiveDateTime {
+        NaiveDate::from_ymd(2023, 3, 26).and_hms(1, 0, 0)
+    }
+
+    fn winter() -> FixedOffset {
+        FixedOffset::east(0)
+    }
+
+    fn summer() -> FixedOffset {
+        FixedOffset::east(3600)
+    }
+}
+
+impl TimeZone for SummerTime {
+    type Offset = FixedOffset;
+
+    fn from_offset(_offset: &FixedOffset) -> Self {
+        SummerTime
+    }
+
+    fn offset_from_local_date(&self, local: &NaiveDate) -> LocalResult<FixedOffset> {
+        self.offset_from_local_datetime(&local.and_hms(12, 0, 0))
+    }
+
+    fn offset_from_local_datetime(&self, local: &NaiveDateTime) -> LocalResult<FixedOffset> {
+        // The clocks skip from 01:00 to 02:00.
+        if *local < SummerTime::switch() {
+            LocalResult::Single(SummerTime::winter())
+        } else if *local >= SummerTime::switch() + Duration::hours(1) {
+            LocalResult::Single(SummerTime::summer())
+        } else {
+            LocalResult::None
+        }
+    }
+
+    fn offset_from_utc_date(&self, utc: &NaiveDate) -> FixedOffset {
+        self.offset_from_utc_datetime(&utc.and_hms(0, 0, 0))
+    }
+
+    fn offset_from_utc_datetime(&self, utc: &NaiveDateTime) -> FixedOffset {
+        if *utc < SummerTime::switch() {
+            SummerTime::winter()
+        } else {
+            SummerTime::summer()
+        }
+    }
+}
+
+#[test]
+fn daylight_saving_time_shifts_occurrences() {
+    let daily = schedule(ScheduleDays::Daily, "08:00:00", Some("10:00:00"));
+
+    assert_eq!(
+        daily.next_start(at("2023-03-25T08:00:00Z"), SummerTime),
+        Some(at("2023-03-26T07:00:00Z"))
+    );
+    assert_eq!(
+        daily.open_occurrence(at("2023-03-26T08:30:00Z"), SummerTime),
+        Some(at("2023-03-26T07:00:00Z"))
+    );
+    // The window closes at 10:00 summer time.
+    assert_eq!(
+        daily.window_end(at("2023-03-26T07:00:00Z"), SummerTime),
+        Some(at("2023-03-26T09:00:00Z"))
+    );
+    assert_eq!(
+        daily.open_occurrence(at("2023-03-26T09:30:00Z"), SummerTime),
+        None
+    );
+    // The day before still ran on winter time.
+    assert_eq!(
+        daily.open_occurrence(at("2023-03-25T09:30:00Z"), SummerTime),
+        Some(at("2023-03-25T08:00:00Z"))
+    );
+}
+
+#[test]
+fn recurring_quests_are_enqueued_once_per_occurrence() {
+    let mut harness = Harness::with_quests(Vec::new());
+    harness
+        .app
+        .world
+        .resource_mut::<QuestScheduler>()
+        .recurring
+        .push(scheduled(
+            "Lunch walk",
+            schedule(ScheduleDays::Daily, "12:30:00", Some("14:00:00")),
+        ));
+    harness.update();
+    assert!(harness.current_quest().is_none());
+
+    let events = harness.advance(Duration::minutes(30));
+    assert_eq!(events.appeared, 1);
+    assert_eq!(harness.current_quest().unwrap().quest.name, "Lunch walk");
+
+    harness.complete_quest();
+    harness.advance(Duration::minutes(10));
+    assert!(harness.current_quest().is_none());
+    assert!(harness.app.world.resource::<QuestPool>().queue.is_empty());
+
+    harness.advance(Duration::days(1));
+    assert_eq!(harness.current_quest().unwrap().quest.name, "Lunch walk");
+    assert_eq!(
+        harness.current_quest().unwrap().deadline,
+        start_time() + Duration::days(1) + Duration::minutes(40) + Duration::hours(1)
+    );
+}
+
+#[test]
+fn recurring_quest_files_fill_the_scheduler() {
+    let mut harness = Harness::with_quest_files();
+    harness.wait_for_quest_pool();
+
+    let scheduler = harness.app.world.resource::<QuestScheduler>();
+    assert_eq!(scheduler.recurring.len(), 3);
+    assert!(scheduler
+        .recurring
+        .iter()
+        .all(|quest| quest.id == quest.name && quest.schedule.is_some()));
+}
+
+#[test]
+fn catch_up_replays_recurring_occurrences() {
+    let mut scheduler = QuestScheduler {
+        recurring: vec![scheduled(
+            "Lunch walk",
+            schedule(ScheduleDays::Daily, "12:30:00", Some("14:00:00")),
+        )],
+        time_zone: ScheduleTimeZone::Fixed(FixedOffset::east(0)),
+        ..Default::default()
+    };
+    scheduler
+        .last_enqueued
+        .insert("Lunch walk".to_string(), at("2023-02-27T12:30:00Z"));
+    let mut current_quest_info = CurrentQuestInfo {
+        last_quest_finished: at("2023-02-27T14:00:00Z"),
+        ..Default::default()
+    };
+    let mut quest_pool = QuestPool::default();
+    let mut tree = TreeInfo::default();
+
+    let missed = catch_up(
+        start_time(),
+        &mut current_quest_info,
+        &mut quest_pool,
+        &mut tree,
+        &DeathRules::default(),
+        &mut scheduler,
+        &mut QuestSelector::default(),
+    );
+
+    // The occurrence of the day before comes up at its start and expires an hour later.
+    assert_eq!(missed.len(), 1);
+    assert_eq!(missed[0].name, "Lunch walk");
+    assert_eq!(missed[0].appeared_at, at("2023-02-28T12:30:00Z"));
+    assert_eq!(missed[0].finished_at, at("2023-02-28T13:30:00Z"));
+    assert_eq!(
+        scheduler.last_enqueued["Lunch walk"],
+        at("2023-02-28T12:30:00Z")
+    );
+    assert!(current_quest_info.active_quests.is_empty());
+}
+
+#[test]
+fn catch_up_skips_occurrences_whose_window_closed() {
+    let mut scheduler = QuestScheduler {
+        recurring: vec![scheduled(
+            "Lunch walk",
+            schedule(ScheduleDays::Daily, "12:30:00", Some("14:00:00")),
+        )],
+        max_active: 1,
+        time_zone: ScheduleTimeZone::Fixed(FixedOffset::east(0)),
+        ..Default::default()
+    };
+    scheduler
+        .last_enqueued
+        .insert("Lunch walk".to_string(), at("2023-02-27T12:30:00Z"));
+    // Takes up the only slot until well after the lunch window.
+    let long_quest = ActiveQuest::new(
+        quest("Paint the fence", Duration::hours(3)),
+        at("2023-02-28T12:00:00Z"),
+    );
+    let mut current_quest_info = CurrentQuestInfo {
+        active_quests: vec![long_quest],
+        last_quest_finished: at("2023-02-28T12:00:00Z"),
+    };
+    let mut quest_pool = QuestPool::default();
+    let mut tree = TreeInfo::default();
+
+    let missed = catch_up(
+        start_time(),
+        &mut current_quest_info,
+        &mut quest_pool,
+        &mut tree,
+        &DeathRules::default(),
+        &mut scheduler,
+        &mut QuestSelector::default(),
+    );
+
+    assert_eq!(missed.len(), 1);
+    assert_eq!(missed[0].name, "Paint the fence");
+    assert!(quest_pool.queue.is_empty());
+    assert!(current_quest_info.active_quests.is_empty());
+}
@@ -0,0 +1,89 @@
+mod common;
+
+use chrono::Duration;
+use common::{quest, quests, Harness};
+use game::data::{
+    CurrentQuestInfo, Difficulty, Quest, QuestCategory, QuestPool, QuestSelector,
+    SelectionStrategy, WeightedRandom,
+};
+
+fn categorized(name: &str, category: QuestCategory) -> Quest {
+    Quest {
+        category,
+        ..quest(name, Duration::seconds(30))
+    }
+}
+
+/// Quest names picked by `strategy`, putting each one back after it's picked.
+fn picks(strategy: &mut dyn SelectionStrategy, quests: &[Quest], count: usize) -> Vec<String> {
+    let candidates: Vec<&Quest> = quests.iter().collect();
+    (0..count)
+        .map(|_| {
+            candidates[strategy.select(&candidates).unwrap()]
+                .name
+                .clone()
+        })
+        .collect()
+}
+
+#[test]
+fn weighted_random_is_reproducible_from_its_seed() {
+    let pool = quests(6);
+
+    let first = picks(&mut WeightedRandom::new(7), &pool, 20);
+    let second = picks(&mut WeightedRandom::new(7), &pool, 20);
+    assert_eq!(first, second);
+    assert_ne!(first, picks(&mut WeightedRandom::new(8), &pool, 20));
+}
+
+#[test]
+fn weighted_random_skips_recent_quests_and_zero_weights() {
+    let pool = vec![
+        categorized("Stretch", QuestCategory::Body),
+        categorized("Read", QuestCategory::Mind),
+        categorized("Walk", QuestCategory::Nature),
+        categorized("Dust", QuestCategory::Home),
+    ];
+    let mut strategy = WeightedRandom::new(1);
+    strategy.category_weights.insert(QuestCategory::Home, 0.0);
+    strategy.difficulty_weights.insert(Difficulty::Medium, 2.0);
+
+    let picked = picks(&mut strategy, &pool, 30);
+    assert!(!picked.contains(&"Dust".to_string()));
+    for window in picked.windows(3) {
+        assert_ne!(window[0], window[1]);
+        assert_ne!(window[0], window[2]);
+    }
+}
+
+#[test]
+fn weighted_random_repeats_rather_than_offering_nothing() {
+    let pool = quests(1);
+    let mut strategy = WeightedRandom::new(3);
+
+    assert_eq!(picks(&mut strategy, &pool, 3), vec!["Quest 0"; 3]);
+}
+
+#[test]
+fn selector_decides_which_quest_appears() {
+    let mut harness = Harness::with_quests(quests(5));
+    harness
+        .app
+        .insert_resource(QuestSelector(Box::new(WeightedRandom::new(11))));
+    harness.update();
+
+    let mut expected = QuestPool {
+        queue: quests(5).into(),
+        ..Default::default()
+    };
+    let mut reference = QuestSelector(Box::new(WeightedRandom::new(11)));
+    for _ in 0..5 {
+        let quest = reference
+            .next(&mut expected, &CurrentQuestInfo::default())
+            .unwrap();
+        assert_eq!(harness.current_quest().unwrap().quest.name, quest.name);
+        harness.complete_quest();
+        harness.advance(Duration::seconds(10));
+    }
+    assert!(harness.current_quest().is_none());
+}
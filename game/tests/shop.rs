@@ -0,0 +1,118 @@
+mod common;
+
+use common::{quest_of, Harness};
+use game::data::{
+    cosmetic, BuyCosmeticEvent, CosmeticSlot, Difficulty, PurchaseError, ToggleCosmeticEvent,
+    Wallet, Wardrobe,
+};
+
+#[test]
+fn completed_quests_earn_leaves_by_difficulty() {
+    for (difficulty, expected) in [
+        (Difficulty::Easy, 6),
+        (Difficulty::Medium, 10),
+        (Difficulty::Hard, 15),
+    ] {
+        let mut harness = Harness::with_quests(vec![quest_of(difficulty)]);
+        harness.update();
+        assert_eq!(harness.resource::<Wallet>().leaves, 0);
+
+        harness.complete_quest();
+        assert_eq!(
+            harness.resource::<Wallet>().leaves,
+            expected,
+            "{:?}",
+            difficulty
+        );
+    }
+}
+
+#[test]
+fn buying_pays_and_puts_the_cosmetic_on() {
+    let mut harness = Harness::with_quests(vec![]);
+    harness.update();
+    harness.resource_mut::<Wallet>().leaves = 100;
+
+    harness.send(BuyCosmeticEvent {
+        id: "clay_pot".to_string(),
+    });
+    assert_eq!(harness.resource::<Wallet>().leaves, 70);
+    assert!(harness.resource::<Wardrobe>().owns("clay_pot"));
+    assert_eq!(
+        harness
+            .resource::<Wardrobe>()
+            .equipped(CosmeticSlot::Pot)
+            .map(|c| c.id),
+        Some("clay_pot")
+    );
+
+    // Too expensive for now.
+    harness.send(BuyCosmeticEvent {
+        id: "glazed_pot".to_string(),
+    });
+    assert_eq!(harness.resource::<Wallet>().leaves, 70);
+    assert!(!harness.resource::<Wardrobe>().owns("glazed_pot"));
+
+    // A second pot replaces the first one in its slot.
+    harness.resource_mut::<Wallet>().leaves = 80;
+    harness.send(BuyCosmeticEvent {
+        id: "glazed_pot".to_string(),
+    });
+    assert_eq!(harness.resource::<Wallet>().leaves, 0);
+    assert_eq!(
+        harness
+            .resource::<Wardrobe>()
+            .equipped(CosmeticSlot::Pot)
+            .map(|c| c.id),
+        Some("glazed_pot")
+    );
+}
+
+#[test]
+fn purchases_are_refused_without_leaves_or_twice() {
+    let mut wallet = Wallet { leaves: 40 };
+    let mut wardrobe = Wardrobe::default();
+
+    assert_eq!(
+        wardrobe.buy("lantern", &mut wallet),
+        Err(PurchaseError::NotEnoughLeaves)
+    );
+    assert_eq!(
+        wardrobe.buy("golden_pot", &mut wallet),
+        Err(PurchaseError::UnknownCosmetic)
+    );
+    assert_eq!(wardrobe.buy("moss", &mut wallet), Ok(()));
+    assert_eq!(
+        wardrobe.buy("moss", &mut wallet),
+        Err(PurchaseError::AlreadyOwned)
+    );
+    assert_eq!(wallet.leaves, 0);
+}
+
+#[test]
+fn owned_cosmetics_toggle_on_and_off() {
+    let mut harness = Harness::with_quests(vec![]);
+    harness.update();
+    harness.resource_mut::<Wallet>().leaves = 100;
+    let skin = cosmetic("autumn_skin").unwrap();
+
+    // Nothing happens for a cosmetic that isn't owned.
+    harness.send(ToggleCosmeticEvent {
+        id: skin.id.to_string(),
+    });
+    assert!(!harness.resource::<Wardrobe>().is_equipped(skin));
+
+    harness.send(BuyCosmeticEvent {
+        id: skin.id.to_string(),
+    });
+    assert!(harness.resource::<Wardrobe>().is_equipped(skin));
+    harness.send(ToggleCosmeticEvent {
+        id: skin.id.to_string(),
+    });
+    assert!(!harness.resource::<Wardrobe>().is_equipped(skin));
+    assert!(harness.resource::<Wardrobe>().owns(skin.id));
+    harness.send(ToggleCosmeticEvent {
+        id: skin.id.to_string(),
+    });
+    assert!(harness.resource::<Wardrobe>().is_equipped(skin));
+}
@@ -0,0 +1,88 @@
+mod common;
+
+use chrono::Duration;
+use common::{quest, quests, Harness};
+use game::data::{
+    xp_for, Difficulty, Profile, Streak, StreakRules, DEFAULT_QUEST_REWARD, XP_PER_STAGE,
+};
+
+#[test]
+fn completions_in_a_row_earn_a_vitality_bonus() {
+    let mut harness = Harness::with_quests(quests(2));
+    harness.update();
+    harness.set_vitality(10.0);
+
+    harness.complete_quest();
+    assert_eq!(harness.resource::<Streak>().current, 1);
+    let vitality = harness.tree().vitality;
+    assert!((vitality - 10.0 - DEFAULT_QUEST_REWARD).abs() < 0.01);
+
+    harness.advance(Duration::seconds(10));
+    harness.complete_quest();
+    assert_eq!(harness.resource::<Streak>().current, 2);
+    let bonus = StreakRules::default().bonus_per_completion;
+    let gained = harness.tree().vitality - vitality;
+    assert!((gained - DEFAULT_QUEST_REWARD * (1.0 + bonus)).abs() < 0.01);
+}
+
+#[test]
+fn a_growth_bonus_that_reaches_a_stage_earns_its_xp() {
+    let mut harness = Harness::with_quests(quests(1));
+    let rules = StreakRules::default();
+    harness.app.insert_resource(Streak {
+        current: rules.growth_bonus_every - 1,
+        ..Default::default()
+    });
+    harness.update();
+    harness.tree_mut().growth.points = 1;
+
+    // One point for the quest and one for the streak reach the first stage at 3.
+    harness.complete_quest();
+
+    assert_eq!(harness.tree().growth.stage, 1);
+    assert_eq!(
+        harness.resource::<Profile>().xp,
+        xp_for(Difficulty::Medium) + XP_PER_STAGE
+    );
+}
+
+#[test]
+fn a_freeze_takes_the_hit_of_one_miss() {
+    let mut harness = Harness::with_quests(vec![
+        quest("Water", Duration::seconds(30)),
+        quest("Prune", Duration::seconds(30)),
+    ]);
+    harness.app.insert_resource(Streak {
+        current: 4,
+        best: 4,
+        freezes: 1,
+    });
+    harness.update();
+
+    assert_eq!(harness.advance(Duration::seconds(31)).missed, 1);
+    assert_eq!(harness.resource::<Streak>().current, 4);
+    assert_eq!(harness.resource::<Streak>().freezes, 0);
+
+    harness.advance(Duration::seconds(10));
+    assert_eq!(harness.advance(Duration::seconds(31)).missed, 1);
+    assert_eq!(harness.resource::<Streak>().current, 0);
+    assert_eq!(harness.resource::<Streak>().best, 4);
+}
+
+#[test]
+fn long_streaks_earn_freezes_up_to_a_limit() {
+    let rules = StreakRules::default();
+    let mut streak = Streak::default();
+
+    for _ in 0..rules.freeze_every {
+        streak.complete(&rules);
+    }
+    assert_eq!(streak.freezes, 1);
+
+    for _ in 0..rules.freeze_every * (rules.max_freezes + 1) {
+        streak.complete(&rules);
+    }
+    assert_eq!(streak.freezes, rules.max_freezes);
+    assert!(streak.miss());
+    assert_eq!(streak.current, rules.freeze_every * (rules.max_freezes + 2));
+}
@@ -0,0 +1,85 @@
+mod common;
+
+use chrono::Duration;
+use common::{quest, start_time, Harness};
+use game::data::{
+    Difficulty, Health, Quest, TreeInfo, DEFAULT_QUEST_PENALTY, MAX_VITALITY,
+    VITALITY_DECAY_PER_DAY,
+};
+
+/// Passive decay takes a sliver of vitality over the few seconds a test spans.
+fn assert_vitality_near(tree: &TreeInfo, expected: f32) {
+    assert!(
+        (tree.vitality - expected).abs() < 0.01,
+        "vitality {} is not near {}",
+        tree.vitality,
+        expected
+    );
+}
+
+#[test]
+fn health_is_derived_from_vitality_thresholds() {
+    assert_eq!(Health::from_vitality(MAX_VITALITY), Health::Good);
+    assert_eq!(Health::from_vitality(50.0), Health::Moderate);
+    assert_eq!(Health::from_vitality(10.0), Health::Bad);
+}
+
+#[test]
+fn vitality_decays_with_wall_clock_time() {
+    let mut tree = TreeInfo {
+        vitality_updated_at: Some(start_time()),
+        ..Default::default()
+    };
+
+    tree.decay(start_time() + Duration::days(2));
+    assert_eq!(tree.vitality, MAX_VITALITY - 2.0 * VITALITY_DECAY_PER_DAY);
+
+    tree.decay(start_time() + Duration::days(30));
+    assert_eq!(tree.vitality, 0.0);
+}
+
+#[test]
+fn quests_apply_their_own_reward_and_penalty() {
+    let mut harness = Harness::with_quests(vec![
+        Quest {
+            penalty: 10.0,
+            ..quest("Water", Duration::seconds(30))
+        },
+        Quest {
+            reward: 4.0,
+            ..quest("Prune", Duration::seconds(30))
+        },
+    ]);
+    harness.update();
+
+    harness.advance(Duration::seconds(31));
+    assert_vitality_near(harness.tree(), MAX_VITALITY - 10.0);
+    assert_eq!(harness.health(), Health::Good);
+
+    harness.advance(Duration::seconds(6));
+    harness.complete_quest();
+    assert_vitality_near(harness.tree(), MAX_VITALITY - 6.0);
+}
+
+#[test]
+fn difficulty_scales_the_outcome_of_a_quest() {
+    let mut harness = Harness::with_quests(vec![
+        Quest {
+            difficulty: Difficulty::Easy,
+            ..quest("Water", Duration::seconds(30))
+        },
+        Quest {
+            difficulty: Difficulty::Hard,
+            ..quest("Prune", Duration::seconds(30))
+        },
+    ]);
+    harness.update();
+
+    harness.advance(Duration::seconds(31));
+    assert_vitality_near(harness.tree(), MAX_VITALITY - DEFAULT_QUEST_PENALTY * 0.6);
+
+    harness.advance(Duration::seconds(6));
+    harness.complete_quest();
+    assert_vitality_near(harness.tree(), MAX_VITALITY);
+    assert_eq!(harness.tree().growth.points, 2);
+}